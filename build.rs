@@ -0,0 +1,57 @@
+//! Generates the list of solution modules that the main binary compiles in with the `registry` feature.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    // solution files are named after their year and two-digit day, e.g. `2024_01.rs`.
+    let mut days: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let day = name.strip_suffix(".rs")?;
+                    let (year, day_number) = day.split_once('_')?;
+                    let is_solution = year.len() == 4
+                        && day_number.len() == 2
+                        && year
+                            .chars()
+                            .chain(day_number.chars())
+                            .all(|c| c.is_ascii_digit());
+                    is_solution.then(|| day.into())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let mut contents = String::new();
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day}.rs"));
+        contents.push_str(&format!(
+            "#[path = {:?}]\nmod day_{day};\n",
+            path.display().to_string()
+        ));
+    }
+
+    contents.push_str(
+        "\npub fn solutions() -> Vec<advent_of_code::template::registry::Solution> {\n    vec![",
+    );
+    contents.push_str(
+        &days
+            .iter()
+            .map(|day| format!("day_{day}::SOLUTION"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    contents.push_str("]\n}\n");
+
+    fs::write(Path::new(&out_dir).join("solutions.rs"), contents).unwrap();
+}
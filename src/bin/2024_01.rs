@@ -0,0 +1,51 @@
+use advent_of_code::parse::{ParseError, Section};
+
+advent_of_code::solution!(1);
+
+fn parse_input(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
+    let [mut numbers1, mut numbers2] = Section::from(input).columns()?;
+    numbers1.sort();
+    numbers2.sort();
+    Ok((numbers1, numbers2))
+}
+
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let (numbers1, numbers2) = parse_input(input)?;
+    let mut diff_sum: i32 = 0;
+    for i in 0..numbers1.len() {
+        diff_sum += (numbers1[i] - numbers2[i]).abs();
+    }
+    Ok(diff_sum)
+}
+
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let (numbers1, numbers2) = parse_input(input)?;
+    let mut similarity: i32 = 0;
+    for num in numbers1.iter() {
+        let count: i32 = numbers2
+            .iter()
+            .filter(|&n| n == num)
+            .count()
+            .try_into()
+            .unwrap();
+        similarity += num * count;
+    }
+    Ok(similarity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(11))
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(31));
+    }
+}
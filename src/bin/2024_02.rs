@@ -1,15 +1,22 @@
-advent_of_code::solution!(2);
+use advent_of_code::parse::{ints, ParseError, Section};
 use itertools::Itertools;
 
-fn parse_input(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .map(|s| s.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
-        })
-        .collect::<Vec<Vec<i32>>>()
+advent_of_code::solution!(2);
+
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    Section::from(input).parse_lines(parse_report)
+}
+
+/// A report needs at least two levels to have a direction.
+fn parse_report(line: &str) -> Result<Vec<i32>, ParseError> {
+    let report = ints(line)?;
+    if report.len() < 2 {
+        return Err(ParseError::new(format!(
+            "expected a report with at least 2 levels, found {}",
+            report.len()
+        )));
+    }
+    Ok(report)
 }
 
 fn get_slopes(input: &[i32]) -> Vec<i32> {
@@ -22,7 +29,8 @@ fn get_slopes(input: &[i32]) -> Vec<i32> {
 
 fn report_is_unsafe_at_edge(report: &[i32]) -> Option<usize> {
     let slopes = get_slopes(report);
-    let increasing = slopes[0] > 0;
+    // dampening a report of two levels leaves a single level, which is safe.
+    let increasing = *slopes.first()? > 0;
     for (i, slope) in slopes.iter().enumerate() {
         if (increasing && *slope < 0)
             || (!increasing && *slope > 0)
@@ -34,19 +42,19 @@ fn report_is_unsafe_at_edge(report: &[i32]) -> Option<usize> {
     None
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let reports = parse_input(input);
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let reports = parse_input(input)?;
     let mut num_safe = 0;
     for report in reports {
         if report_is_unsafe_at_edge(&report).is_none() {
             num_safe += 1;
         }
     }
-    Some(num_safe)
+    Ok(num_safe)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let reports = parse_input(input);
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let reports = parse_input(input)?;
     let mut num_safe = 0;
     for report in reports {
         let res = report_is_unsafe_at_edge(&report);
@@ -76,22 +84,41 @@ pub fn part_two(input: &str) -> Option<u32> {
             }
         }
     }
-    Some(num_safe)
+    Ok(num_safe)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_rejects_short_reports() {
+        let err = part_one("7 6 4 2 1\n\n1 3 2 4 5\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: expected a report with at least 2 levels, found 0"
+        );
+        let err = part_one("7 6 4 2 1\n9\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: expected a report with at least 2 levels, found 1"
+        );
+    }
+
+    #[test]
+    fn test_dampens_two_level_reports() {
+        assert_eq!(part_two("1 9\n"), Ok(1));
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+        assert_eq!(result, Ok(4));
     }
 }
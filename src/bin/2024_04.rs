@@ -0,0 +1,32 @@
+use advent_of_code::grid::Grid;
+use advent_of_code::search::Stencil;
+
+advent_of_code::solution!(4);
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let grid: Grid<char> = input.parse().ok()?;
+    Some(grid.count_word(&['X', 'M', 'A', 'S']))
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let grid: Grid<char> = input.parse().ok()?;
+    let x_mas = Stencil::parse("M.S\n.A.\nM.S", '.').ok()?;
+    Some(grid.count_stencils(&x_mas.variants()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(18));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(9));
+    }
+}
@@ -0,0 +1,134 @@
+use advent_of_code::graph::{CycleError, DiGraph};
+use advent_of_code::parse::{ints, sections, ParseError};
+
+advent_of_code::solution!(5, try_parse = parse);
+
+type Rules = Vec<(usize, usize)>;
+type Updates = Vec<Vec<usize>>;
+
+fn parse_input(input: &str) -> Result<(Rules, Updates), ParseError> {
+    match &sections(input)[..] {
+        [rules, updates] => Ok((rules.pairs()?, updates.parse_lines(parse_update)?)),
+        _ => Err(ParseError::new(
+            "expected rules and updates, separated by a blank line",
+        )),
+    }
+}
+
+/// Every update needs a page, as the parts sum up the middle page of each update.
+fn parse_update(line: &str) -> Result<Vec<usize>, ParseError> {
+    match ints(line)? {
+        update if update.is_empty() => {
+            Err(ParseError::new("expected an update with at least one page"))
+        }
+        update => Ok(update),
+    }
+}
+
+pub struct Input {
+    rules: DiGraph<usize>,
+    updates: Vec<Vec<usize>>,
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let (rules, updates) = parse_input(input)?;
+    Ok(Input {
+        rules: DiGraph::from_edges(rules),
+        updates,
+    })
+}
+
+pub fn part_one(input: &Input) -> Option<usize> {
+    let middles = input
+        .updates
+        .iter()
+        .filter(|update| input.rules.is_ordered(update))
+        .map(|update| update[update.len() / 2]);
+    Some(middles.sum())
+}
+
+pub fn part_two(input: &Input) -> Result<usize, CycleError<usize>> {
+    let sorted_updates: Vec<_> = input
+        .updates
+        .iter()
+        .filter(|update| !input.rules.is_ordered(update))
+        .map(|update| input.rules.topological_sort_subset(update))
+        .collect::<Result<_, _>>()?;
+    let middles = sorted_updates.iter().map(|update| update[update.len() / 2]);
+    Ok(middles.sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::vec;
+
+    use super::*;
+
+    fn before(rules: &DiGraph<usize>, page: usize) -> HashSet<usize> {
+        rules.predecessors(&page).copied().collect()
+    }
+
+    fn after(rules: &DiGraph<usize>, page: usize) -> HashSet<usize> {
+        rules.successors(&page).copied().collect()
+    }
+
+    #[test]
+    fn test_rule_dict_1() {
+        let rules = vec![(1, 2), (2, 3), (3, 4)];
+        let res = DiGraph::from_edges(rules);
+        assert_eq!(before(&res, 1), HashSet::from_iter(vec!()));
+        assert_eq!(after(&res, 1), HashSet::from_iter(vec!(2)));
+        assert_eq!(before(&res, 2), HashSet::from_iter(vec!(1)));
+        assert_eq!(after(&res, 2), HashSet::from_iter(vec!(3)));
+        assert_eq!(before(&res, 3), HashSet::from_iter(vec!(2)));
+        assert_eq!(after(&res, 3), HashSet::from_iter(vec!(4)));
+        assert_eq!(before(&res, 4), HashSet::from_iter(vec!(3)));
+        assert_eq!(after(&res, 4), HashSet::from_iter(vec!()));
+    }
+
+    #[test]
+    fn test_rule_dict_2() {
+        let rules = vec![(1, 2), (1, 3), (2, 3)];
+        let res = DiGraph::from_edges(rules);
+        assert_eq!(before(&res, 1), HashSet::from_iter(vec!()));
+        assert_eq!(after(&res, 1), HashSet::from_iter(vec!(2, 3)));
+        assert_eq!(before(&res, 2), HashSet::from_iter(vec!(1)));
+        assert_eq!(after(&res, 2), HashSet::from_iter(vec!(3)));
+        assert_eq!(before(&res, 3), HashSet::from_iter(vec!(1, 2)));
+        assert_eq!(after(&res, 3), HashSet::from_iter(vec!()));
+    }
+
+    #[test]
+    fn test_rule_dict_3() {
+        let rules = vec![(1, 2), (2, 3), (1, 3)];
+        let res = DiGraph::from_edges(rules);
+        assert_eq!(before(&res, 1), HashSet::from_iter(vec!()));
+        assert_eq!(after(&res, 1), HashSet::from_iter(vec!(2, 3)));
+        assert_eq!(before(&res, 2), HashSet::from_iter(vec!(1)));
+        assert_eq!(after(&res, 2), HashSet::from_iter(vec!(3)));
+        assert_eq!(before(&res, 3), HashSet::from_iter(vec!(2, 1)));
+        assert_eq!(after(&res, 3), HashSet::from_iter(vec!()));
+    }
+
+    #[test]
+    fn test_rejects_empty_updates() {
+        let err = parse("47|53\n\n75,47,61\n-\n").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 4: expected an update with at least one page"
+        );
+    }
+
+    #[test]
+    fn test_part_one() {
+        let input = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(part_one(&input), Some(143));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(part_two(&input), Ok(123));
+    }
+}
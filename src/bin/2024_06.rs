@@ -0,0 +1,67 @@
+use advent_of_code::direction::Dir4;
+use advent_of_code::grid::Grid;
+use advent_of_code::point::Point;
+use advent_of_code::walker::{Outcome, Walker};
+
+advent_of_code::solution!(6);
+
+fn parse_input(input: &str) -> Option<(Grid<char>, Point, Dir4)> {
+    let lab: Grid<char> = input.parse().ok()?;
+    let (start, dir) = lab
+        .iter()
+        .find_map(|(pos, c)| Some((pos, Dir4::try_from(*c).ok()?)))?;
+    Some((lab, start.into(), dir))
+}
+
+fn is_obstructed(c: &char) -> bool {
+    *c == '#'
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let (lab, start, dir) = parse_input(input)?;
+    let walk = Walker::new(&lab, is_obstructed).walk(start, dir);
+    Some(walk.visited.len())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let (lab, start, dir) = parse_input(input)?;
+    let walker = Walker::new(&lab, is_obstructed);
+
+    // a new obstruction can only change the guard's path if it is placed on that path.
+    let candidates = walker.walk(start, dir).visited;
+    let loops = candidates
+        .into_iter()
+        .filter(|pos| *pos != start)
+        .filter(|pos| walker.with_obstacle(*pos).outcome(start, dir) == Outcome::Looped)
+        .count();
+    Some(loops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", DAY, 1,
+        ));
+        assert_eq!(result, Some(41));
+    }
+
+    #[test]
+    fn test_part_one_2() {
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(result, Some(4));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 1,
+        ));
+        assert_eq!(result, Some(6));
+    }
+}
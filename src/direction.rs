@@ -0,0 +1,231 @@
+//! Headings on a grid, in four or eight directions.
+
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::point::Vec2;
+
+/// One of the four orthogonal directions.
+///
+/// ```
+/// # use advent_of_code::direction::Dir4;
+/// assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+/// assert_eq!(Dir4::try_from('<').unwrap(), Dir4::Left);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// All directions, clockwise starting with up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Returns an iterator over all directions, clockwise starting with up.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns the direction after turning 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Returns the direction after turning 90° counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Returns the opposite direction.
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Returns the offset of a single step in this direction.
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::new(0, -1),
+            Self::Right => Vec2::new(1, 0),
+            Self::Down => Vec2::new(0, 1),
+            Self::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    /// Returns the arrow that represents this direction in puzzle inputs, one of `^>v<`.
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+}
+
+/// One of the eight directions, including diagonal ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// All directions, clockwise starting with up.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Returns an iterator over all directions, clockwise starting with up.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns the direction after turning 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 2) % 8]
+    }
+
+    /// Returns the direction after turning 90° counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 6) % 8]
+    }
+
+    /// Returns the opposite direction.
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Returns `true` for the four diagonal directions.
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Returns the offset of a single step in this direction.
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Self::Up => Vec2::new(0, -1),
+            Self::UpRight => Vec2::new(1, -1),
+            Self::Right => Vec2::new(1, 0),
+            Self::DownRight => Vec2::new(1, 1),
+            Self::Down => Vec2::new(0, 1),
+            Self::DownLeft => Vec2::new(-1, 1),
+            Self::Left => Vec2::new(-1, 0),
+            Self::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::ALL[dir as usize * 2]
+    }
+}
+
+impl From<Dir4> for Vec2 {
+    fn from(dir: Dir4) -> Self {
+        dir.offset()
+    }
+}
+
+impl From<Dir8> for Vec2 {
+    fn from(dir: Dir8) -> Self {
+        dir.offset()
+    }
+}
+
+impl TryFrom<char> for Dir4 {
+    type Error = DirFromCharError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' => Ok(Self::Up),
+            '>' => Ok(Self::Right),
+            'v' => Ok(Self::Down),
+            '<' => Ok(Self::Left),
+            _ => Err(DirFromCharError(c)),
+        }
+    }
+}
+
+impl From<Dir4> for char {
+    fn from(dir: Dir4) -> Self {
+        dir.to_char()
+    }
+}
+
+impl Display for Dir4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// An error which can be returned when converting a character to a [`Dir4`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirFromCharError(pub char);
+
+impl Error for DirFromCharError {}
+
+impl Display for DirFromCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid direction `{}`, expecting one of `^>v<`", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8};
+    use crate::point::Vec2;
+    use std::collections::HashSet;
+
+    #[test]
+    fn turns_four_directions() {
+        for dir in Dir4::iter() {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.offset() + dir.reverse().offset(), Vec2::ZERO);
+        }
+        assert_eq!(Dir4::Left.turn_right(), Dir4::Up);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+    }
+
+    #[test]
+    fn turns_eight_directions() {
+        assert_eq!(Dir8::UpLeft.turn_right(), Dir8::UpRight);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::Left);
+        assert_eq!(Dir8::DownLeft.reverse(), Dir8::UpRight);
+        assert_eq!(Dir8::iter().filter(|dir| dir.is_diagonal()).count(), 4);
+        assert_eq!(Dir8::from(Dir4::Down), Dir8::Down);
+
+        let offsets: HashSet<Vec2> = Dir8::iter().map(Dir8::offset).collect();
+        assert_eq!(offsets.len(), 8);
+    }
+
+    #[test]
+    fn converts_chars() {
+        for dir in Dir4::iter() {
+            assert_eq!(Dir4::try_from(dir.to_char()), Ok(dir));
+        }
+        assert_eq!(Dir4::Down.to_string(), "v");
+        assert!(Dir4::try_from('x').is_err());
+    }
+}
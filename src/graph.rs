@@ -0,0 +1,343 @@
+//! Directed graphs for puzzles about dependencies and orderings.
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+/// A directed graph, built from `(from, to)` edges.
+/// Nodes and edges keep their insertion order, so that sorting is deterministic.
+///
+/// ```
+/// # use advent_of_code::graph::DiGraph;
+/// let graph = DiGraph::from_edges([(1, 2), (2, 3), (1, 3)]);
+/// assert_eq!(graph.topological_sort_subset(&[3, 1]), Ok(vec![1, 3]));
+/// assert!(graph.is_ordered(&[1, 3]));
+/// assert!(!graph.is_ordered(&[3, 2]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiGraph<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl<N> Default for DiGraph<N> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            index: HashMap::new(),
+            successors: vec![],
+            predecessors: vec![],
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> DiGraph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a graph from `(from, to)` edges.
+    pub fn from_edges(edges: impl IntoIterator<Item = (N, N)>) -> Self {
+        let mut graph = Self::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    /// Adds a node without edges, if it is not part of the graph yet.
+    pub fn add_node(&mut self, node: N) {
+        self.node_index(node);
+    }
+
+    /// Adds an edge from `from` to `to`, adding both nodes if needed. Duplicate edges are ignored.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let from = self.node_index(from);
+        let to = self.node_index(to);
+
+        if !self.successors[from].contains(&to) {
+            self.successors[from].push(to);
+            self.predecessors[to].push(from);
+        }
+    }
+
+    fn node_index(&mut self, node: N) -> usize {
+        if let Some(&i) = self.index.get(&node) {
+            return i;
+        }
+
+        let i = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.index.insert(node, i);
+        self.successors.push(vec![]);
+        self.predecessors.push(vec![]);
+        i
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.index.contains_key(node)
+    }
+
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        match (self.index.get(from), self.index.get(to)) {
+            (Some(from), Some(to)) => self.successors[*from].contains(to),
+            _ => false,
+        }
+    }
+
+    /// Returns all nodes in insertion order.
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    /// Returns the nodes that `node` has an edge to.
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.edges(node, &self.successors)
+    }
+
+    /// Returns the nodes that have an edge to `node`.
+    pub fn predecessors(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.edges(node, &self.predecessors)
+    }
+
+    fn edges<'a>(&'a self, node: &N, edges: &'a [Vec<usize>]) -> impl Iterator<Item = &'a N> {
+        self.index
+            .get(node)
+            .into_iter()
+            .flat_map(move |i| edges[*i].iter().map(|j| &self.nodes[*j]))
+    }
+
+    /// Returns `true` if no edge of the graph points from a node of `order` to an earlier node of `order`.
+    /// Nodes that are not part of the graph are unconstrained.
+    pub fn is_ordered(&self, order: &[N]) -> bool {
+        let positions: HashMap<usize, usize> = order
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, node)| Some((*self.index.get(node)?, pos)))
+            .collect();
+
+        positions.iter().all(|(node, pos)| {
+            self.successors[*node]
+                .iter()
+                .all(|next| positions.get(next).is_none_or(|next_pos| next_pos > pos))
+        })
+    }
+
+    /// Sorts all nodes of the graph so that every edge points forward, using Kahn's algorithm.
+    /// Returns a cycle if there is no such order.
+    pub fn topological_sort(&self) -> Result<Vec<N>, CycleError<N>> {
+        self.kahn(&(0..self.nodes.len()).collect::<Vec<_>>(), &[])
+    }
+
+    /// Sorts `nodes` so that every edge between them points forward, using Kahn's algorithm.
+    /// Only edges between the given nodes are considered, other nodes of the graph are ignored.
+    /// Nodes that are not part of the graph are unconstrained and placed last, in their given order.
+    /// Returns a cycle if there is no such order.
+    pub fn topological_sort_subset(&self, nodes: &[N]) -> Result<Vec<N>, CycleError<N>> {
+        let mut known = vec![];
+        let mut unknown = vec![];
+
+        for node in nodes {
+            match self.index.get(node) {
+                Some(i) if !known.contains(i) => known.push(*i),
+                Some(_) => {}
+                None if !unknown.contains(node) => unknown.push(node.clone()),
+                None => {}
+            }
+        }
+
+        self.kahn(&known, &unknown)
+    }
+
+    /// Runs Kahn's algorithm on the subgraph induced by `members`, appending `isolated` to the result.
+    fn kahn(&self, members: &[usize], isolated: &[N]) -> Result<Vec<N>, CycleError<N>> {
+        let mut in_subset = vec![false; self.nodes.len()];
+        for i in members {
+            in_subset[*i] = true;
+        }
+
+        let mut in_degree = vec![0; self.nodes.len()];
+        for i in members {
+            in_degree[*i] = self.predecessors[*i]
+                .iter()
+                .filter(|j| in_subset[**j])
+                .count();
+        }
+
+        let mut queue: VecDeque<usize> = members
+            .iter()
+            .copied()
+            .filter(|i| in_degree[*i] == 0)
+            .collect();
+        let mut sorted = Vec::with_capacity(members.len() + isolated.len());
+
+        while let Some(i) = queue.pop_front() {
+            sorted.push(self.nodes[i].clone());
+            for next in &self.successors[i] {
+                if in_subset[*next] {
+                    in_degree[*next] -= 1;
+                    if in_degree[*next] == 0 {
+                        queue.push_back(*next);
+                    }
+                }
+            }
+        }
+
+        if sorted.len() < members.len() {
+            let cycle = self
+                .find_cycle_in(&in_subset)
+                .expect("Kahn's algorithm stopped early, so there has to be a cycle.");
+            return Err(CycleError { cycle });
+        }
+
+        sorted.extend_from_slice(isolated);
+        Ok(sorted)
+    }
+
+    /// Returns a cycle of the graph if there is one, as a path that starts and ends with the same node.
+    pub fn find_cycle(&self) -> Option<Vec<N>> {
+        self.find_cycle_in(&vec![true; self.nodes.len()])
+    }
+
+    /// Depth-first search for a cycle among the nodes that are marked in `members`.
+    fn find_cycle_in(&self, members: &[bool]) -> Option<Vec<N>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.nodes.len()];
+
+        for start in (0..self.nodes.len()).filter(|i| members[*i]) {
+            if state[start] != State::New {
+                continue;
+            }
+
+            // the current path, with the index of the next successor to visit for each node.
+            let mut path: Vec<(usize, usize)> = vec![(start, 0)];
+            state[start] = State::OnPath;
+
+            while let Some((node, edge)) = path.last_mut() {
+                let node = *node;
+                let Some(&next) = self.successors[node].get(*edge) else {
+                    state[node] = State::Done;
+                    path.pop();
+                    continue;
+                };
+                *edge += 1;
+
+                if !members[next] {
+                    continue;
+                }
+
+                match state[next] {
+                    State::New => {
+                        state[next] = State::OnPath;
+                        path.push((next, 0));
+                    }
+                    State::OnPath => {
+                        let from = path.iter().position(|(i, _)| *i == next).unwrap();
+                        let mut cycle: Vec<N> = path[from..]
+                            .iter()
+                            .map(|(i, _)| self.nodes[*i].clone())
+                            .collect();
+                        cycle.push(self.nodes[next].clone());
+                        return Some(cycle);
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for DiGraph<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(iter: I) -> Self {
+        Self::from_edges(iter)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which is returned when nodes cannot be sorted because their edges form a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    /// The nodes of the cycle, starting and ending with the same node.
+    pub cycle: Vec<N>,
+}
+
+impl<N: Debug> Error for CycleError<N> {}
+
+impl<N: Debug> Display for CycleError<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path: Vec<String> = self.cycle.iter().map(|node| format!("{node:?}")).collect();
+        write!(f, "the graph contains a cycle: {}", path.join(" -> "))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DiGraph;
+
+    #[test]
+    fn builds_graphs_from_edges() {
+        let graph: DiGraph<u32> = [(1, 2), (2, 3), (1, 2)].into_iter().collect();
+        assert_eq!(graph.nodes().collect::<Vec<_>>(), [&1, &2, &3]);
+        assert_eq!(graph.successors(&1).collect::<Vec<_>>(), [&2]);
+        assert_eq!(graph.predecessors(&3).collect::<Vec<_>>(), [&2]);
+        assert_eq!(graph.successors(&4).count(), 0);
+        assert!(graph.has_edge(&2, &3));
+        assert!(!graph.has_edge(&3, &2));
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let graph = DiGraph::from_edges([(5, 3), (3, 1), (5, 1), (4, 1)]);
+        assert_eq!(graph.topological_sort(), Ok(vec![5, 4, 3, 1]));
+        assert_eq!(graph.topological_sort_subset(&[1, 5]), Ok(vec![5, 1]));
+        assert_eq!(graph.topological_sort_subset(&[1, 9, 3]), Ok(vec![3, 1, 9]));
+    }
+
+    #[test]
+    fn sorts_subsets_of_cyclic_graphs() {
+        // the rules of a puzzle can be cyclic as a whole, while every subset is not.
+        let graph = DiGraph::from_edges([(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(graph.topological_sort_subset(&[3, 1]), Ok(vec![3, 1]));
+        assert_eq!(graph.topological_sort_subset(&[2, 1]), Ok(vec![1, 2]));
+        assert!(graph.topological_sort().is_err());
+    }
+
+    #[test]
+    fn checks_orders() {
+        let graph = DiGraph::from_edges([(47, 53), (97, 13), (97, 47), (75, 47)]);
+        assert!(graph.is_ordered(&[75, 97, 47, 53, 13]));
+        assert!(graph.is_ordered(&[13, 75]));
+        assert!(!graph.is_ordered(&[53, 47]));
+        assert!(graph.is_ordered(&[]));
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let graph = DiGraph::from_edges([(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+        assert_eq!(graph.find_cycle(), Some(vec![1, 2, 3, 1]));
+
+        let err = graph.topological_sort_subset(&[4, 3, 2, 1]).unwrap_err();
+        assert_eq!(err.cycle, vec![1, 2, 3, 1]);
+        assert_eq!(
+            err.to_string(),
+            "the graph contains a cycle: 1 -> 2 -> 3 -> 1"
+        );
+
+        assert_eq!(DiGraph::from_edges([(1, 2), (2, 3)]).find_cycle(), None);
+        assert_eq!(DiGraph::from_edges([(1, 1)]).find_cycle(), Some(vec![1, 1]));
+    }
+}
@@ -0,0 +1,424 @@
+//! A two-dimensional grid, as used by many puzzles that have a map or a letter matrix as input.
+//!
+//! Positions are `(x, y)` tuples, where `x` is the column and `y` is the row, starting at the top left.
+//! Cells can also be addressed with a [`Point`], which may lie outside the grid.
+
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::direction::{Dir4, Dir8};
+use crate::point::{Point, Vec2};
+
+/// A position in a [`Grid`], given as `(x, y)`.
+pub type Pos = (usize, usize);
+
+/// A value that addresses a cell of a [`Grid`], i.e. a [`Pos`] or a [`Point`].
+pub trait GridIndex {
+    /// Returns the position of the cell, or `None` if it cannot lie within any grid.
+    fn to_pos(self) -> Option<Pos>;
+}
+
+impl GridIndex for Pos {
+    fn to_pos(self) -> Option<Pos> {
+        Some(self)
+    }
+}
+
+impl GridIndex for Point {
+    fn to_pos(self) -> Option<Pos> {
+        Pos::try_from(self).ok()
+    }
+}
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// let grid: Grid<char> = "ab\ncd".parse().unwrap();
+/// assert_eq!(grid[(1, 0)], 'b');
+/// assert_eq!(grid.get((2, 0)), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells that are stored row by row.
+    /// Panics if the number of cells does not match `width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells, got {}",
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid where every cell is `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Creates a grid by calling `f` with the position of every cell, row by row.
+    pub fn from_fn(width: usize, height: usize, f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..width * height).map(|i| (i % width, i / width)).map(f);
+        Self::new(width, height, cells.collect())
+    }
+
+    /// Parses a grid from lines of text, converting every character with `f`.
+    /// Blank lines before and after the grid are ignored, but spaces are cells, even at the edges of the grid.
+    /// Returns an error if the lines are not of equal length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        let lines = input
+            .trim_end_matches(['\n', '\r'])
+            .lines()
+            .enumerate()
+            .skip_while(|(_, line)| line.is_empty());
+
+        for (i, line) in lines {
+            let len = line.chars().count();
+            let expected = *width.get_or_insert(len);
+
+            if len != expected {
+                return Err(ParseGridError {
+                    line: i + 1,
+                    expected,
+                    found: len,
+                });
+            }
+
+            cells.extend(line.chars().map(&mut f));
+            height += 1;
+        }
+
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the index of the cell at `index` in `cells`, if it lies within the grid.
+    fn cell_index(&self, index: impl GridIndex) -> Option<usize> {
+        let (x, y) = index.to_pos()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Returns `true` if `index` lies within the grid.
+    pub fn contains(&self, index: impl GridIndex) -> bool {
+        self.cell_index(index).is_some()
+    }
+
+    pub fn get(&self, index: impl GridIndex) -> Option<&T> {
+        self.cell_index(index).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, index: impl GridIndex) -> Option<&mut T> {
+        self.cell_index(index).map(|i| &mut self.cells[i])
+    }
+
+    /// Returns the position one step of `offset` away from `pos`, if it lies within the grid.
+    /// The offset can be a [`Vec2`], a direction or a `(dx, dy)` tuple.
+    pub fn step(&self, pos: Pos, offset: impl Into<Vec2>) -> Option<Pos> {
+        let next = Point::from(pos) + offset.into();
+        let next = next.to_pos()?;
+        self.contains(next).then_some(next)
+    }
+
+    /// Returns the up to four orthogonal neighbours of `pos` that lie within the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Returns the up to eight neighbours of `pos`, including diagonal ones, that lie within the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::iter().filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Returns all positions of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// Returns all cells of the grid with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the position of the first cell, row by row, that matches `predicate`.
+    pub fn find_position(&self, predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        let i = self.cells.iter().position(predicate)?;
+        Some((i % self.width, i / self.width))
+    }
+
+    /// Creates a grid of the same size by converting every cell with `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Returns a copy of the grid that is rotated by 90° clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Returns a copy of the grid that is mirrored from left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |(x, y)| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Returns the positions from `start` in steps of `step`, until leaving the grid.
+    pub fn line_positions(
+        &self,
+        start: Pos,
+        step: impl Into<Vec2>,
+    ) -> impl Iterator<Item = Pos> + '_ {
+        let step = step.into();
+        let start = self.contains(start).then_some(start);
+        std::iter::successors(start, move |pos| self.step(*pos, step))
+    }
+
+    /// Returns the cells from `start` in steps of `step`, until leaving the grid.
+    pub fn line(&self, start: Pos, step: impl Into<Vec2>) -> impl Iterator<Item = &T> {
+        self.line_positions(start, step).map(|pos| &self[pos])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Returns every row from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.line((x, 0), (0, 1))
+    }
+
+    /// Returns every column from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Returns every diagonal running from the top left to the bottom right,
+    /// starting with the one in the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height)
+            .rev()
+            .map(|y| (0, y))
+            .chain((1..self.width).map(|x| (x, 0)));
+        starts.map(|start| self.line(start, (1, 1)))
+    }
+
+    /// Returns every diagonal running from the top right to the bottom left,
+    /// starting with the one in the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width)
+            .map(|x| (x, 0))
+            .chain((1..self.height).filter_map(|y| Some((self.width.checked_sub(1)?, y))));
+        starts.map(|start| self.line(start, (-1, 1)))
+    }
+}
+
+impl<T, I: GridIndex> Index<I> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: I) -> &Self::Output {
+        self.get(index).expect("position out of bounds")
+    }
+}
+
+impl<T, I: GridIndex> IndexMut<I> for Grid<T> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        self.get_mut(index).expect("position out of bounds")
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, |c| c)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`] with lines of unequal length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGridError {
+    pub line: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} has {} cells, expected {} like the first line",
+            self.line, self.found, self.expected
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::direction::Dir4;
+    use crate::point::Point;
+
+    fn grid() -> Grid<char> {
+        "abcd\nefgh\nijkl".parse().unwrap()
+    }
+
+    fn collect<'a>(line: impl Iterator<Item = &'a char>) -> String {
+        line.collect()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid[(3, 1)], 'h');
+        assert_eq!(grid.get((4, 1)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.to_string(), "abcd\nefgh\nijkl\n");
+
+        let digits = Grid::parse("12\n34\n", |c| c.to_digit(10).unwrap());
+        assert_eq!(digits.unwrap()[(1, 1)], 4);
+    }
+
+    #[test]
+    fn keeps_spaces_at_the_edges() {
+        let grid = "\n\n  #\n # \n#  \n\n".parse::<Grid<char>>().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(2, 0)], '#');
+        assert_eq!(grid[(2, 2)], ' ');
+
+        let err = "\nab\n\ncd".parse::<Grid<char>>().unwrap_err();
+        assert_eq!((err.line, err.expected, err.found), (3, 2, 0));
+    }
+
+    #[test]
+    fn rejects_ragged_grids() {
+        let err = "abc\nde\nfgh".parse::<Grid<char>>().unwrap_err();
+        assert_eq!((err.line, err.expected, err.found), (2, 3, 2));
+    }
+
+    #[test]
+    fn returns_neighbours_within_bounds() {
+        let grid = grid();
+        let corner: Vec<_> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(corner, vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours4((3, 2)).count(), 2);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        assert_eq!(collect(grid.row(1).iter()), "efgh");
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(
+            grid.columns().map(collect).collect::<Vec<_>>(),
+            ["aei", "bfj", "cgk", "dhl"]
+        );
+        assert_eq!(
+            grid.diagonals().map(collect).collect::<Vec<_>>(),
+            ["i", "ej", "afk", "bgl", "ch", "d"]
+        );
+        assert_eq!(
+            grid.anti_diagonals().map(collect).collect::<Vec<_>>(),
+            ["a", "be", "cfi", "dgj", "hk", "l"]
+        );
+        assert_eq!(collect(grid.line((3, 2), (-1, -1))), "lgb");
+    }
+
+    #[test]
+    fn finds_and_updates_cells() {
+        let mut grid = grid();
+        assert_eq!(grid.find_position(|c| *c == 'g'), Some((2, 1)));
+        assert_eq!(grid.find_position(|c| *c == 'z'), None);
+
+        grid[(2, 1)] = '#';
+        assert_eq!(grid.iter().filter(|(_, c)| **c == '#').count(), 1);
+        assert_eq!(grid.step((2, 1), (0, -1)), Some((2, 0)));
+        assert_eq!(grid.step((2, 0), (0, -1)), None);
+        assert!(!grid.map(|c| c.is_alphabetic())[(2, 1)]);
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid();
+        assert_eq!(grid.rotate_right().to_string(), "iea\njfb\nkgc\nlhd\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "dcba\nhgfe\nlkji\n");
+        assert_eq!(
+            grid.rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right(),
+            grid
+        );
+        assert_eq!(Grid::from_fn(3, 2, |(x, y)| x * y).row(1), [0, 1, 2]);
+    }
+
+    #[test]
+    fn accepts_points_and_directions() {
+        let grid = grid();
+        assert_eq!(grid[Point::new(1, 2)], 'j');
+        assert_eq!(grid.get(Point::new(-1, 2)), None);
+        assert!(!grid.contains(Point::new(4, 0)));
+        assert_eq!(grid.step((1, 1), Dir4::Left), Some((0, 1)));
+        assert_eq!(collect(grid.line((1, 2), Dir4::Up)), "jfb");
+    }
+}
@@ -1,3 +1,10 @@
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
+pub mod direction;
+pub mod graph;
+pub mod grid;
+pub mod parse;
+pub mod point;
+pub mod search;
+pub mod walker;
@@ -1,81 +1,287 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, time, verify,
+};
+use advent_of_code::template::registry::Registry;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, Year, YearDay};
 #[cfg(feature = "today")]
 use std::process;
 
+#[cfg(all(feature = "registry", feature = "dhat-heap"))]
+compile_error!("the `registry` feature cannot be combined with `dhat-heap`, as every solution installs its own allocator.");
+
+/// Solutions compiled into this binary, see `build.rs`.
+#[cfg(feature = "registry")]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+/// Returns the in-process solution registry if the `registry` feature is enabled.
+fn registry() -> Option<Registry> {
+    #[cfg(feature = "registry")]
+    return Some(Registry::new(solutions::solutions()));
+
+    #[cfg(not(feature = "registry"))]
+    None
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::time::Comparison;
+    use advent_of_code::template::runner::{BenchConfig, Timeouts};
+    use advent_of_code::template::{all_days, Day, DaySelection, Year, YearDay};
+    use std::collections::HashSet;
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: Vec<YearDay>,
         },
         Read {
-            day: Day,
+            days: Vec<YearDay>,
+        },
+        Examples {
+            days: Vec<YearDay>,
+            overwrite: bool,
+            update_tests: bool,
         },
         Scaffold {
-            day: Day,
+            days: Vec<YearDay>,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            days: Vec<YearDay>,
             release: bool,
             dhat: bool,
+            allocs: bool,
             submit: Option<u8>,
+            time: Option<BenchConfig>,
+            timeouts: Timeouts,
         },
         All {
+            year: Year,
+            days: HashSet<Day>,
             release: bool,
+            timeouts: Timeouts,
+            allocs: bool,
         },
         Time {
-            all: bool,
-            day: Option<Day>,
+            year: Year,
+            days: HashSet<Day>,
             store: bool,
+            bench: BenchConfig,
+            timeouts: Timeouts,
+            allocs: bool,
+            label: Option<String>,
+            compare: Option<Comparison>,
+        },
+        Verify {
+            year: Year,
+            days: Option<HashSet<Day>>,
+            timeouts: Timeouts,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Read `--bench-time`, `--min-samples` and `--max-samples`, falling back to the environment.
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, Box<dyn std::error::Error>> {
+        let time: Option<String> = args.opt_value_from_str("--bench-time")?;
+        let min_samples: Option<String> = args.opt_value_from_str("--min-samples")?;
+        let max_samples: Option<String> = args.opt_value_from_str("--max-samples")?;
+
+        let config = BenchConfig::from_env()?.with_overrides(
+            time.as_deref(),
+            min_samples.as_deref(),
+            max_samples.as_deref(),
+        )?;
+
+        Ok(config)
+    }
+
+    /// Read `--timeout` and `--day-timeout`, falling back to the environment.
+    fn parse_timeouts(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Timeouts, Box<dyn std::error::Error>> {
+        let part: Option<String> = args.opt_value_from_str("--timeout")?;
+        let day: Option<String> = args.opt_value_from_str("--day-timeout")?;
+
+        let timeouts = Timeouts::from_env()?.with_overrides(part.as_deref(), day.as_deref())?;
+
+        Ok(timeouts)
+    }
+
+    /// Read `--compare`, `--baseline` and `--threshold`. A baseline implies `--compare`.
+    fn parse_comparison(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Comparison>, Box<dyn std::error::Error>> {
+        let compare = args.contains("--compare");
+        let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+        let threshold: Option<String> = args.opt_value_from_str("--threshold")?;
+
+        if !compare && baseline.is_none() {
+            if threshold.is_some() {
+                return Err("`--threshold` can only be used with `--compare`.".into());
+            }
+            return Ok(None);
+        }
+
+        Ok(Some(Comparison::new(baseline, threshold.as_deref())?))
+    }
+
+    /// Read `--year`, falling back to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Ok(Year::from_env()
+                .ok_or("no year specified. Pass `--year <year>` or set `AOC_YEAR`.")?),
+        }
+    }
+
+    /// Read a [`DaySelection`] argument, which selects one or more days of `year`'s advent.
+    /// Returns the days in order.
+    fn parse_days(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Vec<YearDay>, Box<dyn std::error::Error>> {
+        let selection: DaySelection = args.free_from_str()?;
+        let mut days: Vec<Day> = selection.resolve(year)?.into_iter().collect();
+
+        if days.is_empty() {
+            return Err("the day selection does not contain any days.".into());
+        }
+
+        days.sort_unstable();
+
+        // days that are not part of the season are rejected here, before a command can create files for them.
+        Ok(days
+            .into_iter()
+            .map(|day| YearDay::try_new(year, day))
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Read an optional [`DaySelection`] argument, which selects days of `year`'s advent.
+    fn parse_opt_days(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Option<HashSet<Day>>, Box<dyn std::error::Error>> {
+        match args.opt_free_from_str::<DaySelection>()? {
+            Some(selection) => Ok(Some(selection.resolve(year)?)),
+            None => Ok(None),
+        }
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+        let subcommand = args.subcommand()?;
+
+        #[cfg(feature = "today")]
+        if subcommand.as_deref() == Some("today") {
+            return Ok(AppArguments::Today);
+        }
+
+        let year = parse_year(&mut args)?;
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let release = args.contains("--release");
+                let allocs = args.contains("--allocs");
+                let timeouts = parse_timeouts(&mut args)?;
+
+                AppArguments::All {
+                    year,
+                    days: parse_opt_days(&mut args, year)?
+                        .unwrap_or_else(|| all_days(year).collect()),
+                    release,
+                    timeouts,
+                    allocs,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let allocs = args.contains("--allocs");
+                let bench = parse_bench_config(&mut args)?;
+                let timeouts = parse_timeouts(&mut args)?;
+                let compare = parse_comparison(&mut args)?;
+                let label = args.opt_value_from_str("--label")?;
+
+                let days = match parse_opt_days(&mut args, year)? {
+                    Some(days) => days,
+                    // a comparison needs the timings of every day, not only of the unsolved ones.
+                    None if all || compare.is_some() => all_days(year).collect(),
+                    // without `--all`, only bench days that are not fully benched yet.
+                    None => DaySelection::Unsolved.resolve(year)?,
+                };
 
                 AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
+                    year,
+                    days,
                     store,
+                    bench,
+                    timeouts,
+                    allocs,
+                    label,
+                    compare,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                days: parse_days(&mut args, year)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                days: parse_days(&mut args, year)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                overwrite: args.contains("--overwrite"),
+                update_tests: args.contains("--update-tests"),
+                days: parse_days(&mut args, year)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                days: parse_days(&mut args, year)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("solve") => {
+                let time = args.contains("--time");
+                let dhat = args.contains("--dhat");
+                let allocs = args.contains("--allocs");
+                let bench = parse_bench_config(&mut args)?;
+                let timeouts = parse_timeouts(&mut args)?;
+                let days = parse_days(&mut args, year)?;
+                let submit = args.opt_value_from_str("--submit")?;
+
+                if submit.is_some() && days.len() > 1 {
+                    return Err("`--submit` can only be used with a single day.".into());
+                }
+
+                if dhat && allocs {
+                    return Err("`--allocs` cannot be combined with `--dhat`.".into());
+                }
+
+                AppArguments::Solve {
+                    days,
+                    release: args.contains("--release"),
+                    submit,
+                    dhat,
+                    allocs,
+                    time: time.then_some(bench),
+                    timeouts,
+                }
+            }
+            Some("verify") => {
+                let timeouts = parse_timeouts(&mut args)?;
+
+                AppArguments::Verify {
+                    year,
+                    days: parse_opt_days(&mut args, year)?,
+                    timeouts,
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -102,29 +308,83 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                days,
+                release,
+                timeouts,
+                allocs,
+            } => all::handle(year, &days, release, &timeouts, allocs, registry().as_ref()),
+            AppArguments::Time {
+                year,
+                days,
+                store,
+                bench,
+                timeouts,
+                allocs,
+                label,
+                compare,
+            } => {
+                time::handle(
+                    year,
+                    &days,
+                    store,
+                    &bench,
+                    &timeouts,
+                    allocs,
+                    label.as_deref(),
+                    compare.as_ref(),
+                    registry().as_ref(),
+                );
+            }
+            AppArguments::Download { days } => days.into_iter().for_each(download::handle),
+            AppArguments::Read { days } => days.into_iter().for_each(read::handle),
+            AppArguments::Examples {
+                days,
+                overwrite,
+                update_tests,
+            } => {
+                for day in days {
+                    examples::handle(day, overwrite, update_tests);
+                }
+            }
             AppArguments::Scaffold {
-                day,
+                days,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+                for day in days {
+                    scaffold::handle(day, overwrite);
+                    if download {
+                        download::handle(day);
+                    }
                 }
             }
             AppArguments::Solve {
-                day,
+                days,
                 release,
                 dhat,
+                allocs,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                time,
+                timeouts,
+            } => {
+                for day in days {
+                    solve::handle(day, release, dhat, allocs, submit, time.as_ref(), &timeouts);
+                }
+            }
+            AppArguments::Verify {
+                year,
+                days,
+                timeouts,
+            } => verify::handle(year, days.as_ref(), &timeouts, registry().as_ref()),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
+                let today = Day::today()
+                    .zip(Year::today())
+                    .and_then(|(day, year)| YearDay::try_new(year, day).ok());
+
+                match today {
                     Some(day) => {
                         scaffold::handle(day, false);
                         download::handle(day);
@@ -132,8 +392,8 @@ fn main() {
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during the advent season. \
+                            Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
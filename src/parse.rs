@@ -0,0 +1,286 @@
+//! Helpers for parsing puzzle inputs, which report the line of the input that could not be parsed.
+//!
+//! ```
+//! # use advent_of_code::parse::{sections, Section};
+//! let [left, right] = Section::from("3   4\n4   3").columns::<u32, 2>().unwrap();
+//! assert_eq!((left, right), (vec![3, 4], vec![4, 3]));
+//!
+//! let input = "47|53\n97|13\n\n75,47,61";
+//! let [rules, updates] = &sections(input)[..] else { panic!() };
+//! assert_eq!(rules.pairs::<u32>().unwrap(), vec![(47, 53), (97, 13)]);
+//! assert_eq!(updates.ints::<u32>().unwrap(), vec![vec![75, 47, 61]]);
+//! ```
+
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// An error which is returned when a puzzle input cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the input, starting at 1. Not set for errors of a single line.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    fn at_line(line: usize, message: impl Display) -> Self {
+        Self {
+            line: Some(line),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An integer type that can be extracted from text with [`ints`].
+pub trait Integer: FromStr + Copy {
+    /// Whether a `-` in front of the digits is read as the sign of the integer.
+    const SIGNED: bool;
+}
+
+macro_rules! integer {
+    ($signed:literal: $($t:ty),*) => {
+        $(impl Integer for $t {
+            const SIGNED: bool = $signed;
+        })*
+    };
+}
+
+integer!(true: i8, i16, i32, i64, i128, isize);
+integer!(false: u8, u16, u32, u64, u128, usize);
+
+/// Extracts all integers from `line`, ignoring any other characters.
+/// For signed types, a `-` directly in front of the digits is a sign, unless it follows a digit
+/// (so that e.g. `3-5` is read as `3` and `5`).
+/// Returns an error if an integer does not fit into `T`.
+pub fn ints<T: Integer>(line: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = line.as_bytes();
+    let mut values = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut start = i;
+        if T::SIGNED
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit())
+        {
+            start -= 1;
+        }
+
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let value = line[start..i].parse().map_err(|_| {
+            ParseError::new(format!(
+                "`{}` does not fit into {}",
+                &line[start..i],
+                std::any::type_name::<T>()
+            ))
+        })?;
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+/// Extracts exactly `N` integers from `line`, see [`ints`].
+pub fn array<T: Integer, const N: usize>(line: &str) -> Result<[T; N], ParseError> {
+    let values = ints(line)?;
+    let found = values.len();
+    values
+        .try_into()
+        .map_err(|_| ParseError::new(format!("expected {N} integers, found {found}")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A block of lines of the puzzle input, which knows the line number it starts at.
+/// Errors of its parse methods point to the line in the whole input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// The number of the first line of the section within the input, starting at 1.
+    pub first_line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> From<&'a str> for Section<'a> {
+    /// Creates a section of the whole input. Trailing whitespace is ignored.
+    fn from(input: &'a str) -> Self {
+        Self {
+            first_line: 1,
+            text: input.trim_end(),
+        }
+    }
+}
+
+/// Splits the input into sections that are separated by blank lines.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = vec![];
+    // the line number and byte offset where the current section starts.
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+
+        match start {
+            None if !is_blank => start = Some((i + 1, offset)),
+            Some((first_line, from)) if is_blank => {
+                sections.push(Section::at(first_line, &input[from..offset]));
+                start = None;
+            }
+            _ => {}
+        }
+
+        offset += line.len();
+    }
+
+    if let Some((first_line, from)) = start {
+        sections.push(Section::at(first_line, &input[from..]));
+    }
+
+    sections
+}
+
+impl<'a> Section<'a> {
+    fn at(first_line: usize, text: &'a str) -> Self {
+        Self {
+            first_line,
+            text: text.trim_end(),
+        }
+    }
+
+    /// Returns every line of the section together with its line number.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> {
+        let first_line = self.first_line;
+        self.text
+            .lines()
+            .enumerate()
+            .map(move |(i, line)| (first_line + i, line))
+    }
+
+    /// Parses every line with `f`. Returns the first error together with its line number.
+    pub fn parse_lines<T, E: Display>(
+        &self,
+        mut f: impl FnMut(&'a str) -> Result<T, E>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.lines()
+            .map(|(number, line)| f(line).map_err(|e| ParseError::at_line(number, e)))
+            .collect()
+    }
+
+    /// Extracts all integers of every line, see [`ints`].
+    pub fn ints<T: Integer>(&self) -> Result<Vec<Vec<T>>, ParseError> {
+        self.parse_lines(ints)
+    }
+
+    /// Extracts exactly `N` integers of every line, see [`ints`].
+    pub fn arrays<T: Integer, const N: usize>(&self) -> Result<Vec<[T; N]>, ParseError> {
+        self.parse_lines(array)
+    }
+
+    /// Extracts exactly two integers of every line, see [`ints`].
+    pub fn pairs<T: Integer>(&self) -> Result<Vec<(T, T)>, ParseError> {
+        self.parse_lines(|line| array(line).map(|[a, b]| (a, b)))
+    }
+
+    /// Extracts exactly `N` integers of every line and returns them as `N` columns, see [`ints`].
+    pub fn columns<T: Integer, const N: usize>(&self) -> Result<[Vec<T>; N], ParseError> {
+        let rows = self.arrays::<T, N>()?;
+        Ok(std::array::from_fn(|i| {
+            rows.iter().map(|row| row[i]).collect()
+        }))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{array, ints, sections, ParseError, Section};
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(ints::<i32>("7 6 4 2 1"), Ok(vec![7, 6, 4, 2, 1]));
+        assert_eq!(ints::<i64>("x=-3, y=12 -> 5-2"), Ok(vec![-3, 12, 5, 2]));
+        assert_eq!(ints::<u8>("x=-3, y=12"), Ok(vec![3, 12]));
+        assert_eq!(ints::<u32>("no numbers"), Ok(vec![]));
+        assert!(ints::<u8>("256").is_err());
+    }
+
+    #[test]
+    fn extracts_arrays() {
+        assert_eq!(array::<usize, 2>("47|53"), Ok([47, 53]));
+        assert_eq!(
+            array::<usize, 3>("47|53").unwrap_err().to_string(),
+            "expected 3 integers, found 2"
+        );
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "\n1|2\n3|4\n\n\n5,6\n7\n";
+        let sections = sections(input);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].first_line, 2);
+        assert_eq!(sections[0].text, "1|2\n3|4");
+        assert_eq!(sections[1].first_line, 6);
+        assert_eq!(
+            sections[1].lines().collect::<Vec<_>>(),
+            [(6, "5,6"), (7, "7")]
+        );
+    }
+
+    #[test]
+    fn reports_line_numbers() {
+        let input = "1|2\n3|4\n\n5,6\n7\n";
+        let err = sections(input)[1].pairs::<u32>().unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                line: Some(5),
+                message: "expected 2 integers, found 1".into()
+            }
+        );
+        assert_eq!(err.to_string(), "line 5: expected 2 integers, found 1");
+
+        let err = Section::from("1 2\n3").parse_lines(|line| line.parse::<u32>());
+        assert_eq!(err.unwrap_err().line, Some(1));
+    }
+
+    #[test]
+    fn parses_columns() {
+        let [left, right] = Section::from("3   4\n4   3\n2   5\n")
+            .columns::<i32, 2>()
+            .unwrap();
+        assert_eq!(left, [3, 4, 2]);
+        assert_eq!(right, [4, 3, 5]);
+    }
+}
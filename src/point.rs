@@ -0,0 +1,181 @@
+//! Points and offsets on a two-dimensional plane.
+//!
+//! Like in [`Grid`](crate::grid::Grid), `x` grows to the right and `y` grows downwards.
+
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::Pos;
+
+/// A point on the plane. Unlike a grid [`Pos`], a point can have negative coordinates,
+/// so that stepping off a grid does not underflow.
+///
+/// ```
+/// # use advent_of_code::point::{Point, Vec2};
+/// let point = Point::new(2, 3) + Vec2::new(-1, 0) * 3;
+/// assert_eq!(point, Point::new(-1, 3));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the manhattan distance between two points.
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+/// An offset between two points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Vec2 {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Pos> for Point {
+    fn from((x, y): Pos) -> Self {
+        Self::new(x as isize, y as isize)
+    }
+}
+
+impl TryFrom<Point> for Pos {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        Ok((usize::try_from(point.x)?, usize::try_from(point.y)?))
+    }
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Add<Vec2> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vec2) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vec2> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vec2) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign<Vec2> for Point {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Self::Output {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Vec2) -> Self::Output {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Self::Output {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Point, Vec2};
+    use crate::grid::Pos;
+
+    #[test]
+    fn does_arithmetic() {
+        let mut point = Point::new(3, 4);
+        point += Vec2::new(1, -1);
+        assert_eq!(point, Point::new(4, 3));
+        point -= Vec2::new(5, 0);
+        assert_eq!(point, Point::new(-1, 3));
+        assert_eq!(Point::new(4, 3) - Point::ORIGIN, Vec2::new(4, 3));
+        assert_eq!(-Vec2::new(1, -2) * 2 + Vec2::new(1, 1), Vec2::new(-1, 5));
+        assert_eq!(Point::new(-1, 3).manhattan(Point::new(2, 0)), 6);
+    }
+
+    #[test]
+    fn converts_grid_positions() {
+        assert_eq!(Point::from((2, 5)), Point::new(2, 5));
+        assert_eq!(Pos::try_from(Point::new(2, 5)), Ok((2, 5)));
+        assert!(Pos::try_from(Point::new(-1, 5)).is_err());
+    }
+}
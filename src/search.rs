@@ -0,0 +1,171 @@
+//! Word searches and pattern matching on a [`Grid`].
+
+use crate::direction::Dir8;
+use crate::grid::{Grid, ParseGridError, Pos};
+
+impl<T: PartialEq> Grid<T> {
+    /// Returns the start position and direction of every occurrence of `word`,
+    /// which can run along any of the eight directions.
+    /// A word of a single cell is found once per matching cell, not once per direction.
+    pub fn find_word<'a>(&'a self, word: &'a [T]) -> impl Iterator<Item = (Pos, Dir8)> + 'a {
+        let directions = if word.len() > 1 { 8 } else { 1 };
+
+        self.positions()
+            .filter(move |pos| word.first().is_some_and(|first| self[*pos] == *first))
+            .flat_map(move |pos| {
+                Dir8::iter()
+                    .take(directions)
+                    .filter(move |dir| {
+                        let mut line = self.line(pos, *dir);
+                        word.iter().all(|cell| line.next() == Some(cell))
+                    })
+                    .map(move |dir| (pos, dir))
+            })
+    }
+
+    /// Counts the occurrences of `word` along any of the eight directions.
+    ///
+    /// ```
+    /// # use advent_of_code::grid::Grid;
+    /// let grid: Grid<char> = "XMAS\nMM..\nA.A.\nS..S".parse().unwrap();
+    /// assert_eq!(grid.count_word(&['X', 'M', 'A', 'S']), 3);
+    /// ```
+    pub fn count_word(&self, word: &[T]) -> usize {
+        self.find_word(word).count()
+    }
+
+    /// Returns the top left position of every placement of `stencil` that matches the grid.
+    pub fn find_stencil<'a>(&'a self, stencil: &'a Stencil<T>) -> impl Iterator<Item = Pos> + 'a {
+        let width = (self.width() + 1).saturating_sub(stencil.cells.width());
+        let height = (self.height() + 1).saturating_sub(stencil.cells.height());
+
+        (0..height)
+            .flat_map(move |y| (0..width).map(move |x| (x, y)))
+            .filter(move |pos| stencil.matches(self, *pos))
+    }
+
+    /// Counts the placements of `stencil` that match the grid.
+    pub fn count_stencil(&self, stencil: &Stencil<T>) -> usize {
+        self.find_stencil(stencil).count()
+    }
+
+    /// Counts the placements of all `stencils` that match the grid,
+    /// e.g. of all [variants](Stencil::variants) of a pattern.
+    pub fn count_stencils(&self, stencils: &[Stencil<T>]) -> usize {
+        stencils.iter().map(|s| self.count_stencil(s)).sum()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular pattern of cells, where empty cells match anything.
+///
+/// ```
+/// # use advent_of_code::{grid::Grid, search::Stencil};
+/// let grid: Grid<char> = "M.S\n.A.\nM.S".parse().unwrap();
+/// let x_mas = Stencil::parse("M.S\n.A.\nM.S", '.').unwrap();
+/// assert_eq!(x_mas.variants().len(), 4);
+/// assert_eq!(grid.count_stencils(&x_mas.variants()), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stencil<T> {
+    cells: Grid<Option<T>>,
+}
+
+impl<T: PartialEq> Stencil<T> {
+    /// Creates a stencil from a grid, where `None` cells are wildcards.
+    pub fn new(cells: Grid<Option<T>>) -> Self {
+        Self { cells }
+    }
+
+    /// Returns `true` if the stencil matches `grid` when placed with its top left corner at `pos`.
+    pub fn matches(&self, grid: &Grid<T>, (x, y): Pos) -> bool {
+        self.cells.iter().all(|((dx, dy), cell)| match cell {
+            Some(cell) => grid.get((x + dx, y + dy)) == Some(cell),
+            None => true,
+        })
+    }
+
+    /// Returns the distinct rotations and reflections of the stencil, including itself.
+    pub fn variants(&self) -> Vec<Self>
+    where
+        T: Clone,
+    {
+        let mut variants: Vec<Self> = vec![];
+        let mut cells = self.cells.clone();
+
+        for _ in 0..4 {
+            for variant in [cells.clone(), cells.flip_horizontal()] {
+                let variant = Self::new(variant);
+                if !variants.contains(&variant) {
+                    variants.push(variant);
+                }
+            }
+            cells = cells.rotate_right();
+        }
+
+        variants
+    }
+}
+
+impl Stencil<char> {
+    /// Parses a stencil from lines of text, where `wildcard` matches any character.
+    pub fn parse(input: &str, wildcard: char) -> Result<Self, ParseGridError> {
+        let cells = Grid::parse(input, |c| (c != wildcard).then_some(c))?;
+        Ok(Self::new(cells))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stencil;
+    use crate::direction::Dir8;
+    use crate::grid::Grid;
+
+    const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
+
+    fn grid() -> Grid<char> {
+        "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn counts_words_in_all_directions() {
+        assert_eq!(grid().count_word(&XMAS), 18);
+
+        let grid: Grid<char> = "SAMXMAS".parse().unwrap();
+        let found: Vec<_> = grid.find_word(&XMAS).collect();
+        assert_eq!(found, vec![((3, 0), Dir8::Right), ((3, 0), Dir8::Left)]);
+    }
+
+    #[test]
+    fn counts_short_words_once() {
+        let grid: Grid<char> = "XAX".parse().unwrap();
+        assert_eq!(grid.count_word(&['X']), 2);
+        assert_eq!(grid.count_word(&[]), 0);
+        assert_eq!(grid.count_word(&['X', 'A', 'X', 'A']), 0);
+    }
+
+    #[test]
+    fn matches_stencils_with_wildcards() {
+        let x_mas = Stencil::parse("M.S\n.A.\nM.S", '.').unwrap();
+        assert_eq!(grid().count_stencil(&x_mas), 2);
+        assert_eq!(grid().count_stencils(&x_mas.variants()), 9);
+        assert_eq!(x_mas.variants()[0], x_mas);
+    }
+
+    #[test]
+    fn returns_distinct_variants() {
+        let square = Stencil::parse("ab\nba", '.').unwrap();
+        assert_eq!(square.variants().len(), 2);
+
+        let corner = Stencil::parse("ab\nc.", '.').unwrap();
+        assert_eq!(corner.variants().len(), 8);
+
+        let large = Stencil::parse("XXXXXXXXXXX", '.').unwrap();
+        assert_eq!(grid().count_stencils(&large.variants()), 0);
+    }
+}
@@ -0,0 +1,558 @@
+/// Built-in client for the Advent of Code website.
+///
+/// The client authenticates with the same session cookie as aoc-cli. It reads `AOC_SESSION`, then
+/// the file named by `AOC_SESSION_FILE`, then `~/.adventofcode.session`. Requests go to
+/// `AOC_BASE_URL`, which defaults to the Advent of Code website.
+///
+/// Set `AOC_BACKEND=aoc-cli` to use the external aoc-cli binary instead.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{aoc_cli, get_input_path, get_puzzle_path, YearDay};
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const SESSION_FILE_ENV: &str = "AOC_SESSION_FILE";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const BACKEND_ENV: &str = "AOC_BACKEND";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    UnknownBackend(String),
+    Request(String),
+    BadStatus(u16),
+    Io(io::Error),
+    Cli(aoc_cli::AocCommandError),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or write it to \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::UnknownBackend(x) => write!(
+                f,
+                "unknown backend `{x}` in {BACKEND_ENV}. Expected `native` or `aoc-cli`."
+            ),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::BadStatus(404) => write!(
+                f,
+                "the puzzle was not found. It might not be unlocked yet."
+            ),
+            AocClientError::BadStatus(400) => write!(
+                f,
+                "the server rejected the request. The session cookie might have expired."
+            ),
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Io(e) => write!(f, "{e}"),
+            AocClientError::Cli(aoc_cli::AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            AocClientError::Cli(e) => write!(f, "failed to call aoc-cli: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Request(e.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    /// Create a client from the session cookie and base URL configured in the environment.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    pub fn download_input(&self, day: YearDay) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(day));
+        self.get(&url)
+    }
+
+    /// Download the description of a puzzle as markdown. The second part is only included once
+    /// the first part has been solved.
+    pub fn download_puzzle(&self, day: YearDay) -> Result<String, AocClientError> {
+        Ok(puzzle_to_markdown(&self.get(&self.day_url(day))?))
+    }
+
+    /// Submit an answer and return the message of the server's response.
+    pub fn submit(&self, day: YearDay, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        Ok(response_message(&response))
+    }
+
+    fn day_url(&self, day: YearDay) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            day.year,
+            day.day.into_inner()
+        )
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?
+            .into_string()?)
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session.trim().to_string()).filter(|s| !s.is_empty());
+    }
+
+    let path = env::var_os(SESSION_FILE_ENV)
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(SESSION_FILE_NAME)))?;
+
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The service used to talk to the Advent of Code website, selected with `AOC_BACKEND`.
+pub enum Backend {
+    /// The built-in [`AocClient`]. This is the default.
+    Native(AocClient),
+    /// The external aoc-cli binary.
+    AocCli,
+}
+
+impl Backend {
+    pub fn from_env() -> Result<Self, AocClientError> {
+        match env::var(BACKEND_ENV).as_deref() {
+            Ok("native") | Err(_) => AocClient::from_env().map(Backend::Native),
+            Ok("aoc-cli") => {
+                aoc_cli::check().map_err(AocClientError::Cli)?;
+                Ok(Backend::AocCli)
+            }
+            Ok(x) => Err(AocClientError::UnknownBackend(x.into())),
+        }
+    }
+
+    /// Download the input and description of a puzzle to `data/`.
+    pub fn download(&self, day: YearDay) -> Result<(), AocClientError> {
+        let input_path = get_input_path(day);
+        let puzzle_path = get_puzzle_path(day);
+        create_parent_dir(&input_path)?;
+        create_parent_dir(&puzzle_path)?;
+
+        match self {
+            Backend::Native(client) => {
+                fs::write(&input_path, client.download_input(day)?)?;
+                fs::write(&puzzle_path, client.download_puzzle(day)?)?;
+
+                println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+                println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+                Ok(())
+            }
+            Backend::AocCli => aoc_cli::download(day)
+                .map(|_| ())
+                .map_err(AocClientError::Cli),
+        }
+    }
+
+    /// Download the description of a puzzle to `data/puzzles` and print it to the terminal.
+    pub fn read(&self, day: YearDay) -> Result<(), AocClientError> {
+        let puzzle_path = get_puzzle_path(day);
+        create_parent_dir(&puzzle_path)?;
+
+        match self {
+            Backend::Native(client) => {
+                let puzzle = client.download_puzzle(day)?;
+                fs::write(&puzzle_path, &puzzle)?;
+                println!("{puzzle}");
+                Ok(())
+            }
+            Backend::AocCli => aoc_cli::read(day).map(|_| ()).map_err(AocClientError::Cli),
+        }
+    }
+
+    /// Submit an answer and return the server's response, which is also printed to the terminal.
+    pub fn submit(&self, day: YearDay, part: u8, answer: &str) -> Result<String, AocClientError> {
+        match self {
+            Backend::Native(client) => {
+                let message = client.submit(day, part, answer)?;
+                println!("{message}");
+                Ok(message)
+            }
+            Backend::AocCli => {
+                let output = aoc_cli::submit(day, part, answer).map_err(AocClientError::Cli)?;
+                Ok(format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                ))
+            }
+        }
+    }
+}
+
+fn create_parent_dir(path: &str) -> io::Result<()> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Convert the puzzle articles of a day's page to markdown, similar to the files aoc-cli writes.
+fn puzzle_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut rest = html;
+
+    // articles hold the puzzle parts, the answers of solved parts follow them in a paragraph.
+    loop {
+        let next = [
+            ("<article", "</article>"),
+            ("<p>Your puzzle answer was", "</p>"),
+        ]
+        .into_iter()
+        .filter_map(|(open, close)| rest.find(open).map(|i| (i, close)))
+        .min();
+
+        let Some((start, close)) = next else {
+            break;
+        };
+
+        let end = rest[start..]
+            .find(close)
+            .map_or(rest.len(), |i| start + i + close.len());
+
+        markdown.push_str(&html_to_markdown(&rest[start..end]));
+        rest = &rest[end..];
+    }
+
+    format!("{}\n", markdown.trim_end())
+}
+
+fn html_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let tag_start = rest.find('<').unwrap_or(rest.len());
+        let text = decode_entities(&rest[..tag_start]);
+
+        if in_pre {
+            markdown.push_str(&text);
+        } else if !text.trim().is_empty() || !markdown.ends_with(['\n', ' ']) {
+            markdown.push_str(&text.replace('\n', " "));
+        }
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (name, is_closing) {
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            // markup inside of code blocks can't be represented in markdown.
+            (_, _) if in_pre => {}
+            ("h2", false) => markdown.push('\\'),
+            ("h2", true) => markdown.push_str("\n----------\n\n"),
+            ("p", true) | ("ul", true) => {
+                markdown.truncate(markdown.trim_end_matches([' ', '\n']).len());
+                markdown.push_str("\n\n");
+            }
+            ("li", false) => markdown.push_str("* "),
+            ("li", true) => markdown.push('\n'),
+            ("code", _) => markdown.push('`'),
+            ("em", _) => markdown.push('*'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                markdown.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                markdown.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    markdown
+}
+
+/// Extract the text of the first article of a page, which holds the message of a response.
+fn response_message(html: &str) -> String {
+    let article = html
+        .find("<article")
+        .and_then(|start| {
+            let end = html[start..].find("</article>")?;
+            Some(&html[start..start + end])
+        })
+        .unwrap_or(html);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_to_markdown, AocClient, AocClientError};
+    use crate::template::YearDay;
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    const PUZZLE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is always present.</p>
+<p>For example:</p>
+<pre><code>3   4
+4   <em>3</em>
+</code></pre>
+<ul>
+<li>The smallest number is <code>1</code>.</li>
+</ul>
+<p>In the example above, this is <code>2 + 1</code>, a total distance of <code><em>11</em></code>!</p>
+</article>
+<p>Your puzzle answer was <code>1834060</code>.</p><p>The first half is complete! <a href="/2024/day/1#part2">Continue</a>.</p>
+</main>"#;
+
+    /// Serve a single request with the given status and body, returning the URL of the server and
+    /// a handle that resolves to the raw request.
+    fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(x) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = x.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (url, handle) = serve_once("200 OK", "3   4\n4   3\n");
+        let client = AocClient::new(&url, "abc");
+
+        let input = client
+            .download_input(YearDay::new(year!(2024), day!(1)))
+            .unwrap();
+        assert_eq!(input, "3   4\n4   3\n");
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn downloads_puzzle() {
+        let (url, handle) = serve_once("200 OK", PUZZLE);
+        let client = AocClient::new(&url, "abc");
+
+        let puzzle = client
+            .download_puzzle(YearDay::new(year!(2024), day!(1)))
+            .unwrap();
+        assert!(handle
+            .join()
+            .unwrap()
+            .starts_with("GET /2024/day/1 HTTP/1.1"));
+        assert_eq!(puzzle, puzzle_to_markdown(PUZZLE));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, handle) = serve_once(
+            "200 OK",
+            "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "abc");
+
+        let message = client
+            .submit(YearDay::new(year!(2024), day!(1)), 2, "42")
+            .unwrap();
+        assert_eq!(
+            message,
+            "That's the right answer! You are one gold star closer."
+        );
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (url, _) = serve_once("404 Not Found", "");
+        let client = AocClient::new(&url, "abc");
+
+        let result = client.download_input(YearDay::new(year!(2024), day!(25)));
+        assert!(matches!(result, Err(AocClientError::BadStatus(404))));
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        assert_eq!(
+            puzzle_to_markdown(PUZZLE),
+            "\\--- Day 1: Historian Hysteria ---
+----------
+
+The *Chief Historian* is always present.
+
+For example:
+
+```
+3   4
+4   3
+```
+
+* The smallest number is `1`.
+
+In the example above, this is `2 + 1`, a total distance of `*11*`!
+
+Your puzzle answer was `1834060`.
+"
+        );
+    }
+}
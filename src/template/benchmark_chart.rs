@@ -0,0 +1,251 @@
+/// Renders the benchmark timings as an SVG bar chart, which `cargo time --store` embeds in the readme.
+///
+/// Every day gets a bar per part on a logarithmic time axis, so that solutions that take nanoseconds
+/// and solutions that take seconds fit into the same chart.
+use std::{env, fs};
+
+use crate::template::timings::Timings;
+use crate::template::Year;
+
+pub const BENCH_CHART_ENV: &str = "AOC_BENCH_CHART";
+
+const HEIGHT: f64 = 320.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 40.0;
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const DAY_WIDTH: f64 = 32.0;
+/// Charts of a few days are stretched to this width, so that the legend fits.
+const MIN_PLOT_WIDTH: f64 = 240.0;
+const BAR_WIDTH: f64 = 11.0;
+/// Text and grid color, which is readable on both light and dark backgrounds.
+const AXIS_COLOR: &str = "#8b949e";
+const PART_COLORS: [(u8, &str); 2] = [(1, "#d1423f"), (2, "#3f9c4c")];
+
+/// Whether the chart is enabled with `AOC_BENCH_CHART`.
+pub fn is_enabled() -> bool {
+    env::var(BENCH_CHART_ENV).is_ok_and(|v| matches!(v.as_str(), "1" | "true"))
+}
+
+/// Path of the chart of a year, relative to the repository root.
+pub fn get_chart_path(year: Year) -> String {
+    format!(".assets/benchmarks-{year}.svg")
+}
+
+/// Formats a power of ten of nanoseconds as an axis label, e.g. `100µs`.
+fn format_decade(exponent: i32) -> String {
+    let (unit, base) = match exponent {
+        ..=2 => ("ns", 0),
+        3..=5 => ("µs", 3),
+        6..=8 => ("ms", 6),
+        _ => ("s", 9),
+    };
+    format!("{}{unit}", 10_u64.pow((exponent - base).unsigned_abs()))
+}
+
+/// A logarithmic axis from `10^min` to `10^max` nanoseconds.
+struct Scale {
+    min: i32,
+    max: i32,
+}
+
+impl Scale {
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(nanos: impl Iterator<Item = f64>) -> Option<Self> {
+        let (lo, hi) = nanos.fold((f64::INFINITY, 0_f64), |(lo, hi), n| (lo.min(n), hi.max(n)));
+
+        if !lo.is_finite() {
+            return None;
+        }
+
+        let min = lo.max(1.0).log10().floor() as i32;
+        let max = (hi.max(1.0).log10().ceil() as i32).max(min + 1);
+        Some(Scale { min, max })
+    }
+
+    /// The y coordinate of a time.
+    fn y(&self, nanos: f64) -> f64 {
+        let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        let share = (nanos.max(1.0).log10() - f64::from(self.min)) / f64::from(self.max - self.min);
+        HEIGHT - MARGIN_BOTTOM - share * plot_height
+    }
+}
+
+/// Render the part 1 and part 2 times of every day. Returns `None` if no part has a time.
+#[allow(clippy::cast_precision_loss)]
+pub fn render(timings: &Timings) -> Option<String> {
+    let scale = Scale::new(
+        timings
+            .data
+            .iter()
+            .flat_map(|t| PART_COLORS.iter().filter_map(|(part, _)| t.nanos(*part))),
+    )?;
+
+    let plot_width = (timings.data.len() as f64 * DAY_WIDTH).max(MIN_PLOT_WIDTH);
+    let day_width = plot_width / timings.data.len() as f64;
+    let width = MARGIN_LEFT + plot_width + MARGIN_RIGHT;
+    let left = MARGIN_LEFT;
+    let right = width - MARGIN_RIGHT;
+    let bottom = HEIGHT - MARGIN_BOTTOM;
+
+    let mut svg = String::new();
+    let mut line = |s: String| {
+        svg.push_str(&s);
+        svg.push('\n');
+    };
+
+    line(format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{HEIGHT}" viewBox="0 0 {width} {HEIGHT}" font-family="sans-serif" font-size="11" fill="{AXIS_COLOR}">"#
+    ));
+
+    for (i, (part, color)) in PART_COLORS.iter().enumerate() {
+        let x = left + i as f64 * 64.0;
+        line(format!(
+            r#"<rect x="{x}" y="12" width="10" height="10" fill="{color}"/><text x="{}" y="21">Part {part}</text>"#,
+            x + 14.0
+        ));
+    }
+
+    for exponent in scale.min..=scale.max {
+        let y = scale.y(10_f64.powi(exponent));
+        line(format!(
+            r#"<line x1="{left}" y1="{y:.1}" x2="{right}" y2="{y:.1}" stroke="{AXIS_COLOR}" stroke-opacity="0.3"/>"#
+        ));
+        line(format!(
+            r#"<text x="{}" y="{:.1}" text-anchor="end">{}</text>"#,
+            left - 6.0,
+            y + 4.0,
+            format_decade(exponent)
+        ));
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        let center = left + (i as f64 + 0.5) * day_width;
+
+        for (j, (part, color)) in PART_COLORS.iter().enumerate() {
+            let Some(nanos) = timing.nanos(*part) else {
+                continue;
+            };
+            let x = center - BAR_WIDTH + j as f64 * BAR_WIDTH;
+            let y = scale.y(nanos);
+            let time = match *part {
+                1 => timing.part_1.as_deref(),
+                _ => timing.part_2.as_deref(),
+            };
+            line(format!(
+                r#"<rect x="{x:.1}" y="{y:.1}" width="{BAR_WIDTH}" height="{:.1}" fill="{color}"><title>Day {} Part {part}: {}</title></rect>"#,
+                bottom - y,
+                timing.day,
+                time.unwrap_or_default()
+            ));
+        }
+
+        line(format!(
+            r#"<text x="{center:.1}" y="{}" text-anchor="middle">{}</text>"#,
+            bottom + 16.0,
+            timing.day.into_inner()
+        ));
+    }
+
+    line(format!(
+        r#"<line x1="{left}" y1="{bottom}" x2="{right}" y2="{bottom}" stroke="{AXIS_COLOR}"/>"#
+    ));
+    line(format!(
+        r#"<text x="{:.1}" y="{}" text-anchor="middle">Day</text>"#,
+        (left + right) / 2.0,
+        HEIGHT - 6.0
+    ));
+    line("</svg>".into());
+
+    Some(svg)
+}
+
+/// Write the chart of `year` to `.assets/`. Returns the path of the chart, or `None` if there is nothing to chart.
+pub fn update(year: Year, timings: &Timings) -> Option<String> {
+    let svg = render(timings)?;
+    let path = get_chart_path(year);
+
+    match fs::create_dir_all(".assets").and_then(|()| fs::write(&path, svg)) {
+        Ok(()) => Some(path),
+        Err(e) => {
+            eprintln!("Failed to write the benchmark chart to {path}: {e}");
+            None
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_decade, render, Scale, HEIGHT, MARGIN_BOTTOM, MARGIN_TOP};
+    use crate::day;
+    use crate::template::runner::Statistic;
+    use crate::template::timings::{Timing, Timings};
+    use crate::template::Day;
+
+    fn timing(day: Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            parse: None,
+            failed: vec![],
+            timed_out: vec![],
+            allocs: vec![],
+            nanos: vec![],
+            total_nanos: 0.0,
+            statistic: Statistic::Mean,
+        }
+    }
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(2), "100ns");
+        assert_eq!(format_decade(4), "10µs");
+        assert_eq!(format_decade(6), "1ms");
+        assert_eq!(format_decade(10), "10s");
+    }
+
+    #[test]
+    fn scales_logarithmically() {
+        let scale = Scale::new([250.0, 1.5e6].into_iter()).unwrap();
+        assert_eq!((scale.min, scale.max), (2, 7));
+        assert!((scale.y(100.0) - (HEIGHT - MARGIN_BOTTOM)).abs() < 1e-9);
+        assert!((scale.y(1e7) - MARGIN_TOP).abs() < 1e-9);
+        assert!(scale.y(1e4) < scale.y(1e3));
+
+        let scale = Scale::new([1000.0].into_iter()).unwrap();
+        assert_eq!((scale.min, scale.max), (3, 4));
+        assert!(Scale::new(std::iter::empty()).is_none());
+    }
+
+    #[test]
+    fn renders_bars_per_part() {
+        let timings = Timings {
+            data: vec![
+                timing(day!(1), Some("12.5µs"), Some("1.2ms")),
+                timing(day!(2), Some("300.0ns"), None),
+            ],
+        };
+
+        let svg = render(&timings).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains("<title>Day 01 Part 2: 1.2ms</title>"));
+        assert!(svg.contains(">100ns</text>"));
+        assert!(svg.contains(">10ms</text>"));
+        assert!(!svg.contains(">1ns</text>"));
+    }
+
+    #[test]
+    fn skips_empty_charts() {
+        let timings = Timings {
+            data: vec![timing(day!(1), None, None)],
+        };
+        assert!(render(&timings).is_none());
+        assert!(render(&Timings::default()).is_none());
+    }
+}
@@ -1,5 +1,15 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::collections::HashSet;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::runner::Timeouts;
+use crate::template::{registry::Registry, run_multi::run_multi, Day, Year};
+
+pub fn handle(
+    year: Year,
+    days: &HashSet<Day>,
+    is_release: bool,
+    timeouts: &Timeouts,
+    allocs: bool,
+    registry: Option<&Registry>,
+) {
+    run_multi(year, days, is_release, None, timeouts, allocs, registry);
 }
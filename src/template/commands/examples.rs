@@ -0,0 +1,184 @@
+use std::{fs, process};
+
+use crate::template::puzzle::{parse_puzzle, PuzzlePart};
+use crate::template::{get_bin_path, get_data_dir, get_example_path, get_puzzle_path, YearDay};
+
+/// An example input and the file it is written to.
+struct Example {
+    path: String,
+    contents: String,
+    /// Part suffix of the file (e.g. `2` for `01-2.txt`), if there is more than one example.
+    file_part: Option<usize>,
+}
+
+fn collect_examples(day: YearDay, parts: &[PuzzlePart]) -> Vec<Example> {
+    let mut unique: Vec<&String> = vec![];
+
+    for example in parts.iter().flat_map(|p| &p.examples) {
+        if !unique.contains(&example) {
+            unique.push(example);
+        }
+    }
+
+    let is_single = unique.len() == 1;
+
+    unique
+        .into_iter()
+        .enumerate()
+        .map(|(i, contents)| {
+            let file_part = (!is_single).then_some(i + 1);
+            let path = match file_part {
+                Some(k) => format!("{}/examples/{}-{k}.txt", get_data_dir(day.year), day.day),
+                None => get_example_path(day),
+            };
+            Example {
+                path,
+                contents: format!("{contents}\n"),
+                file_part,
+            }
+        })
+        .collect()
+}
+
+fn write_example(example: &Example, overwrite: bool) {
+    let is_empty = fs::read_to_string(&example.path).map_or(true, |s| s.trim().is_empty());
+
+    if !is_empty && !overwrite {
+        println!(
+            "Skipped \"{}\" as it is not empty. Use `--overwrite` to replace it.",
+            example.path
+        );
+        return;
+    }
+
+    match fs::write(&example.path, &example.contents) {
+        Ok(()) => println!("Wrote example to \"{}\"", example.path),
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn format_expected(answer: &str) -> String {
+    if answer.parse::<i64>().is_ok() {
+        format!("Some({answer})")
+    } else {
+        format!("Some({answer:?})")
+    }
+}
+
+fn format_read_call(file_part: Option<usize>) -> String {
+    match file_part {
+        Some(k) => format!("read_file_part(\"examples\", DAY, {k})"),
+        None => "read_file(\"examples\", DAY)".into(),
+    }
+}
+
+/// Replace the placeholder assertion of a scaffolded test with the proposed one.
+/// Returns `None` if the test has already been edited.
+fn update_test(
+    module: &str,
+    test_name: &str,
+    file_part: Option<usize>,
+    expected: &str,
+) -> Option<String> {
+    let start = module.find(&format!("fn {test_name}()"))?;
+    let end = start + module[start..].find("\n    }\n")?;
+    let body = &module[start..end];
+
+    if !body.contains("assert_eq!(result, None);") {
+        return None;
+    }
+
+    let new_body = body
+        .replace(
+            "assert_eq!(result, None);",
+            &format!("assert_eq!(result, {expected});"),
+        )
+        .replace("read_file(\"examples\", DAY)", &format_read_call(file_part));
+
+    Some(format!(
+        "{}{}{}",
+        &module[..start],
+        new_body,
+        &module[end..]
+    ))
+}
+
+/// Extract example inputs and answers from a downloaded puzzle description.
+pub fn handle(day: YearDay, overwrite: bool, update_tests: bool) {
+    let puzzle_path = get_puzzle_path(day);
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not read \"{puzzle_path}\". Run `cargo download {} --year {}` first.",
+            day.day, day.year
+        );
+        process::exit(1);
+    };
+
+    let parts = parse_puzzle(&markdown);
+    let examples = collect_examples(day, &parts);
+
+    if examples.is_empty() {
+        println!("No examples found in \"{puzzle_path}\".");
+    }
+
+    for example in &examples {
+        write_example(example, overwrite);
+    }
+
+    let module_path = get_bin_path(day);
+    let mut module = fs::read_to_string(&module_path).ok();
+
+    println!("---");
+
+    for (i, (part, test_name)) in parts
+        .iter()
+        .zip(["test_part_one", "test_part_two"])
+        .enumerate()
+    {
+        let Some(answer) = &part.answer else {
+            continue;
+        };
+
+        // parts without an example of their own usually reuse the previous one.
+        let example = parts[..=i]
+            .iter()
+            .rev()
+            .find_map(|p| p.examples.first())
+            .and_then(|contents| examples.iter().find(|e| e.contents.trim_end() == contents));
+
+        let file_part = example.and_then(|e| e.file_part);
+        let expected = format_expected(answer);
+
+        println!(
+            "🎄 Proposed {test_name}: `{}` should be `{expected}`.",
+            format_read_call(file_part)
+        );
+
+        if update_tests {
+            let updated = module
+                .as_deref()
+                .and_then(|m| update_test(m, test_name, file_part, &expected));
+
+            match updated {
+                Some(updated) => {
+                    module = Some(updated);
+                    println!("   Updated {test_name} in \"{module_path}\".");
+                }
+                None => println!("   Skipped {test_name}, it is missing or was already edited."),
+            }
+        }
+    }
+
+    if update_tests {
+        if let Some(module) = module {
+            if let Err(e) = fs::write(&module_path, module) {
+                eprintln!("Failed to update module file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -1,11 +1,44 @@
-use std::process::{Command, Stdio};
+use std::fs;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::heap_report::{report_path, HeapReport};
+use crate::template::protocol::PARSE_PART;
+use crate::template::runner::{BenchConfig, Timeouts};
+use crate::template::{get_bin_path, YearDay};
+
+/// Steps of a solution that DHAT writes a report for.
+const PROFILED_PARTS: [u8; 3] = [PARSE_PART, 1, 2];
+
+/// Run the solution of a single day. Parts are benched with `bench` if it is set.
+/// With `allocs`, the solution is built with the `alloc-stats` feature and reports its allocations.
+pub fn handle(
+    day: YearDay,
+    release: bool,
+    dhat: bool,
+    allocs: bool,
+    submit_part: Option<u8>,
+    bench: Option<&BenchConfig>,
+    timeouts: &Timeouts,
+) {
+    if !Path::new(&get_bin_path(day)).exists() {
+        eprintln!(
+            "No solution found at \"{}\". Run `cargo scaffold {} --year {}` to create it.",
+            get_bin_path(day),
+            day.day,
+            day.year
+        );
+        process::exit(1);
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
+        // reports of a previous run would be summarized for steps that do not run anymore.
+        for part in PROFILED_PARTS {
+            let _ = fs::remove_file(report_path(part));
+        }
+
         cmd_args.extend([
             "--profile".to_string(),
             "dhat".to_string(),
@@ -16,6 +49,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if allocs {
+        cmd_args.extend(["--features".to_string(), "alloc-stats".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +60,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(bench) = bench {
+        cmd_args.push("--time".to_string());
+        cmd_args.extend(bench.to_args());
+    }
+
+    cmd_args.extend(timeouts.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -31,4 +75,30 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .unwrap();
 
     cmd.wait().unwrap();
+
+    if dhat {
+        print_heap_reports();
+    }
+}
+
+/// Print a summary of the DHAT report of every step that ran.
+fn print_heap_reports() {
+    for part in PROFILED_PARTS {
+        let path = report_path(part);
+
+        if !Path::new(&path).exists() {
+            continue;
+        }
+
+        let label = match part {
+            PARSE_PART => "Parse".to_string(),
+            part => format!("Part {part}"),
+        };
+
+        println!();
+        match HeapReport::read_from_file(Path::new(&path)) {
+            Ok(report) => report.print(&label),
+            Err(e) => eprintln!("Could not read DHAT report \"{path}\": {e}"),
+        }
+    }
 }
@@ -1,34 +1,108 @@
 use std::collections::HashSet;
+use std::{env, process};
 
+use crate::template::history::{current_commit, print_comparison, History, Run};
+use crate::template::registry::Registry;
 use crate::template::run_multi::run_multi;
+use crate::template::runner::{BenchConfig, Statistic, Timeouts};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
-
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
-
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
+use crate::template::{benchmark_chart, readme_benchmarks, Day, Year};
+
+pub const BENCH_THRESHOLD_ENV: &str = "AOC_BENCH_THRESHOLD";
+
+/// Compares a run with an earlier run from the timings history.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Comparison {
+    /// The label or commit of the run to compare against. Compares against the previous run if unset.
+    pub baseline: Option<String>,
+    /// Fail if a part got slower by more than this many percent.
+    pub threshold: Option<f64>,
+}
+
+impl Comparison {
+    /// Reads the threshold from `threshold`, falling back to `AOC_BENCH_THRESHOLD`.
+    pub fn new(baseline: Option<String>, threshold: Option<&str>) -> Result<Self, String> {
+        let threshold = match threshold {
+            Some(s) => Some(s.to_string()),
+            None => env::var(BENCH_THRESHOLD_ENV).ok(),
+        };
+
+        let threshold = threshold
+            .map(|s| {
+                s.trim_end_matches('%')
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|t| t.is_finite() && *t >= 0.0)
+                    .ok_or(format!(
+                        "invalid regression threshold `{s}`, expecting a percentage like `10`."
+                    ))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            baseline,
+            threshold,
+        })
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    days: &HashSet<Day>,
+    store: bool,
+    bench: &BenchConfig,
+    timeouts: &Timeouts,
+    allocs: bool,
+    label: Option<&str>,
+    compare: Option<&Comparison>,
+    registry: Option<&Registry>,
+) {
+    let stored_timings = Timings::read_from_file(year);
+    let history = History::read_from_file(year);
+
+    // resolve the baseline first, so that a typo does not cost a full bench run.
+    let baseline = compare.map(|c| match history.baseline(c.baseline.as_deref()) {
+        Some(run) => run,
+        None => {
+            match &c.baseline {
+                Some(name) => eprintln!("No run labelled `{name}` or on commit `{name}` in the timings history."),
+                None => eprintln!("No previous run in the timings history. Run `cargo time` once to record a baseline."),
             }
-        },
-        |day| HashSet::from([day]),
+            process::exit(1);
+        }
+    });
+
+    let timings = run_multi(year, days, true, Some(bench), timeouts, allocs, registry).unwrap();
+
+    let run = Run::from_timings(
+        &timings,
+        Statistic::from_env(),
+        current_commit(),
+        label.map(Into::into),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // runs that benched no parts, e.g. because all days are stored already, are not recorded.
+    if !run.parts.is_empty() {
+        if let Err(e) = History::append(year, &run) {
+            eprintln!("Failed to record the run in the timings history: {e}");
+        }
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        if let Err(e) = merged_timings.store_file(year) {
+            eprintln!("Failed to store timings: {e}");
+        }
+
+        let chart = if benchmark_chart::is_enabled() {
+            benchmark_chart::update(year, &merged_timings)
+        } else {
+            None
+        };
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings, chart.as_deref()) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +111,17 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if let (Some(baseline), Some(compare)) = (baseline, compare) {
+        println!();
+        let regressions = print_comparison(baseline, &run, compare.threshold);
+
+        if regressions > 0 {
+            eprintln!(
+                "\n{regressions} part(s) got slower by more than {}%.",
+                compare.threshold.unwrap_or_default()
+            );
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,141 @@
+use std::collections::HashSet;
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::registry::Registry;
+use crate::template::run_multi::run_day;
+use crate::template::runner::Timeouts;
+use crate::template::{all_days, Day, Year, YearDay, ANSI_BOLD, ANSI_RESET};
+
+/// Outcome of comparing a part's result with its stored answer.
+enum Outcome {
+    Pass,
+    Fail,
+    /// No answer is stored for the part yet.
+    Unknown,
+}
+
+struct Check {
+    day: Day,
+    part: u8,
+    expected: Option<String>,
+    actual: Option<String>,
+    outcome: Outcome,
+}
+
+fn check(day: Day, part: u8, expected: Option<&str>, actual: Option<&str>) -> Check {
+    let outcome = match expected {
+        None => Outcome::Unknown,
+        Some(expected) if Some(expected) == actual => Outcome::Pass,
+        Some(_) => Outcome::Fail,
+    };
+
+    Check {
+        day,
+        part,
+        expected: expected.map(Into::into),
+        actual: actual.map(Into::into),
+        outcome,
+    }
+}
+
+/// Shortens an answer to a single table cell.
+fn format_cell(answer: Option<&String>) -> String {
+    match answer {
+        None => "-".into(),
+        Some(answer) if answer.contains('\n') || answer.chars().count() > 20 => {
+            let first_line = answer.lines().next().unwrap_or_default();
+            format!("{}…", first_line.chars().take(19).collect::<String>())
+        }
+        Some(answer) => answer.clone(),
+    }
+}
+
+fn print_table(checks: &[Check]) {
+    println!(
+        "{ANSI_BOLD}Day | Part | Expected             | Actual               | Result{ANSI_RESET}"
+    );
+
+    for check in checks {
+        let result = match check.outcome {
+            Outcome::Pass => "✔ pass",
+            Outcome::Fail => "✖ fail",
+            Outcome::Unknown => "? no stored answer",
+        };
+
+        println!(
+            "{}  | {:<4} | {:<20} | {:<20} | {result}",
+            check.day,
+            check.part,
+            format_cell(check.expected.as_ref()),
+            format_cell(check.actual.as_ref()),
+        );
+    }
+}
+
+/// Run solutions against their real inputs and compare the results with the stored answers.
+/// Without `days`, every day that has a stored answer is verified.
+pub fn handle(
+    year: Year,
+    days: Option<&HashSet<Day>>,
+    timeouts: &Timeouts,
+    registry: Option<&Registry>,
+) {
+    let answers = Answers::read_from_file(year);
+
+    let days: Vec<Day> = match days {
+        Some(days) => all_days(year).filter(|day| days.contains(day)).collect(),
+        None => answers.days(),
+    };
+
+    if days.is_empty() {
+        println!("No answers stored yet. Answers are stored when `cargo solve <day> --submit <part>` submits a correct result.");
+        return;
+    }
+
+    let mut checks: Vec<Check> = vec![];
+
+    for day in days {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let output = run_day(
+            YearDay::new(year, day),
+            true,
+            None,
+            timeouts,
+            false,
+            registry,
+        );
+
+        for part in [1, 2] {
+            let expected = answers.get(day, part);
+            let actual = output
+                .records
+                .iter()
+                .find(|r| r.part == part)
+                .and_then(|r| r.answer.as_deref());
+
+            // skip parts that neither have a stored answer nor an implementation.
+            if expected.is_none() && actual.is_none() {
+                continue;
+            }
+
+            checks.push(check(day, part, expected, actual));
+        }
+
+        println!();
+    }
+
+    print_table(&checks);
+
+    let failed = checks
+        .iter()
+        .filter(|c| matches!(c.outcome, Outcome::Fail))
+        .count();
+
+    if failed > 0 {
+        eprintln!("\n{failed} part(s) did not match their stored answer.");
+        process::exit(1);
+    }
+}
@@ -2,6 +2,8 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
@@ -9,6 +11,8 @@ use chrono::{Datelike, FixedOffset, Utc};
 const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Whether a day is part of a specific year's season is checked by [`Year::has_day`](crate::template::Year::has_day),
+/// see [`Year::last_day`](crate::template::Year::last_day) for the length of each season.
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -38,9 +42,25 @@ impl Day {
     }
 
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Parses a day of `year`'s advent, which rejects days after the last day of its season.
+    ///
+    /// ```
+    /// # use advent_of_code::{Day, Year};
+    /// let year = Year::new(2025).unwrap();
+    /// assert!(Day::parse_in("12", year).is_ok());
+    /// assert!(Day::parse_in("13", year).is_err());
+    /// ```
+    pub fn parse_in(s: &str, year: Year) -> Result<Self, DayFromStrError> {
+        let last_day = year.last_day().into_inner();
+        let err = || DayFromStrError { last_day };
+
+        let day = s.parse().map_err(|_| err())?;
+        Self::new(day).filter(|_| day <= last_day).ok_or_else(err)
+    }
 }
 
 #[cfg(feature = "today")]
@@ -77,43 +97,50 @@ impl PartialOrd<u8> for Day {
 
 /* -------------------------------------------------------------------------- */
 
+/// Parses a day of any season, i.e. between 1 and 25. Use [`Day::parse_in`] for a specific year.
 impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        let err = || DayFromStrError { last_day: 25 };
+        let day = s.parse().map_err(|_| err())?;
+        Self::new(day).ok_or_else(err)
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
 #[derive(Debug)]
-pub struct DayFromStrError;
+pub struct DayFromStrError {
+    last_day: u8,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", self.last_day)
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of `year`'s advent, from the 1st to its last day.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of a year's advent, from the 1st to its last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.last_day().into_inner(),
+        }
     }
 }
 
@@ -121,10 +148,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,8 +162,22 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// With a year, e.g. `day!(12, 2025)`, the day also has to be part of that year's season.
 #[macro_export]
 macro_rules! day {
+    ($day:expr, $year:expr) => {{
+        const _ASSERT: () = assert!(
+            $crate::year!($year).has_day($crate::day!($day)),
+            concat!(
+                "invalid day number `",
+                $day,
+                "`, expecting a day of the ",
+                $year,
+                " season"
+            ),
+        );
+        $crate::template::Day::__new_unchecked($day)
+    }};
     ($day:expr) => {{
         const _ASSERT: () = assert!(
             $day != 0 && $day <= 25,
@@ -155,10 +196,11 @@ macro_rules! day {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{all_days, Day};
+    use crate::year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(year!(2024));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +229,25 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_days_of_a_season() {
+        assert_eq!(Day::parse_in("25", year!(2024)).unwrap(), Day(25));
+        assert_eq!(Day::parse_in("12", year!(2025)).unwrap(), Day(12));
+        assert_eq!(crate::day!(12, 2025), Day(12));
+
+        let err = Day::parse_in("13", year!(2025)).unwrap_err();
+        assert_eq!(err.to_string(), "expecting a day number between 1 and 12");
+        assert!(Day::parse_in("0", year!(2024)).is_err());
+        assert!(Day::parse_in("x", year!(2024)).is_err());
+    }
+
+    #[test]
+    fn all_days_iterator_for_short_seasons() {
+        let days: Vec<_> = all_days(year!(2025)).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
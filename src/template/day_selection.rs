@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day, DayOutOfSeasonError, Year};
+
+/// A set of days passed to a command on the command line.
+///
+/// A selection is either a comma-separated list of days and ranges (e.g. `1-5`, `1,3,7` or `1-3,7`),
+/// or one of the keywords `odd`, `even`, `complete` and `unsolved`.
+/// A day is `complete` if timings for both of its parts are stored, all other days are `unsolved`.
+///
+/// ```
+/// # use advent_of_code::DaySelection;
+/// assert!("1-3,7".parse::<DaySelection>().is_ok());
+/// assert!("5-3".parse::<DaySelection>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelection {
+    Days(Vec<Day>),
+    Odd,
+    Even,
+    Complete,
+    Unsolved,
+}
+
+impl DaySelection {
+    /// Returns the selected days of `year`'s advent.
+    /// Explicitly listed days that are not part of the advent return an error.
+    pub fn resolve(&self, year: Year) -> Result<HashSet<Day>, DayOutOfSeasonError> {
+        let days = match self {
+            Self::Days(days) => {
+                if let Some(&day) = days.iter().find(|day| !year.has_day(**day)) {
+                    return Err(DayOutOfSeasonError { year, day });
+                }
+                days.iter().copied().collect()
+            }
+            Self::Odd => all_days(year)
+                .filter(|day| day.into_inner() % 2 == 1)
+                .collect(),
+            Self::Even => all_days(year)
+                .filter(|day| day.into_inner() % 2 == 0)
+                .collect(),
+            Self::Complete => {
+                let timings = Timings::read_from_file(year);
+                all_days(year)
+                    .filter(|day| timings.is_day_complete(*day))
+                    .collect()
+            }
+            Self::Unsolved => {
+                let timings = Timings::read_from_file(year);
+                all_days(year)
+                    .filter(|day| !timings.is_day_complete(*day))
+                    .collect()
+            }
+        };
+
+        Ok(days)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySelection {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "odd" => return Ok(Self::Odd),
+            "even" => return Ok(Self::Even),
+            "complete" => return Ok(Self::Complete),
+            "unsolved" => return Ok(Self::Unsolved),
+            _ => {}
+        }
+
+        let err = || DaySelectionFromStrError(s.to_string());
+        let mut days = vec![];
+
+        for item in s.split(',') {
+            match item.split_once('-') {
+                Some((from, to)) => {
+                    let from: Day = from.trim().parse().map_err(|_| err())?;
+                    let to: Day = to.trim().parse().map_err(|_| err())?;
+                    if from > to {
+                        return Err(err());
+                    }
+                    days.extend((from.into_inner()..=to.into_inner()).map(Day::__new_unchecked));
+                }
+                None => days.push(item.trim().parse().map_err(|_| err())?),
+            }
+        }
+
+        days.sort_unstable();
+        days.dedup();
+
+        Ok(Self::Days(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug)]
+pub struct DaySelectionFromStrError(String);
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting days between 1 and 25 (e.g. `3`, `1-5` or `1,3,7`), `odd`, `even`, `complete` or `unsolved`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySelection;
+    use crate::{day, year};
+    use std::collections::HashSet;
+
+    fn resolve(s: &str) -> Vec<u8> {
+        let days = s.parse::<DaySelection>().unwrap();
+        let mut days: Vec<u8> = days
+            .resolve(year!(2024))
+            .unwrap()
+            .into_iter()
+            .map(|day| day.into_inner())
+            .collect();
+        days.sort_unstable();
+        days
+    }
+
+    #[test]
+    fn parses_days_ranges_and_lists() {
+        assert_eq!(resolve("4"), vec![4]);
+        assert_eq!(resolve("1-5"), vec![1, 2, 3, 4, 5]);
+        assert_eq!(resolve("1,3,7"), vec![1, 3, 7]);
+        assert_eq!(resolve("7,1-3,2"), vec![1, 2, 3, 7]);
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!(resolve("odd").len(), 13);
+        assert_eq!(resolve("even").first(), Some(&2));
+        assert_eq!(
+            "unsolved".parse::<DaySelection>().unwrap(),
+            DaySelection::Unsolved
+        );
+        assert_eq!(
+            "complete".parse::<DaySelection>().unwrap(),
+            DaySelection::Complete
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_selections() {
+        for s in ["", "0", "26", "5-3", "1-", "1,,2", "all"] {
+            assert!(s.parse::<DaySelection>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn rejects_days_out_of_season() {
+        let days = "10-13".parse::<DaySelection>().unwrap();
+        assert!(days.resolve(year!(2025)).is_err());
+        assert_eq!(
+            DaySelection::Odd.resolve(year!(2025)).unwrap(),
+            HashSet::from([day!(1), day!(3), day!(5), day!(7), day!(9), day!(11)])
+        );
+    }
+}
@@ -0,0 +1,273 @@
+/// Summaries of the DHAT reports that `cargo solve --dhat` writes for every part.
+///
+/// Reports use DHAT's JSON format: every allocation site ("program point") lists its totals and the
+/// frames of its backtrace, which are indices into a shared frame table.
+use std::{collections::HashMap, fs, path::Path, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc::format_bytes;
+use crate::template::protocol::PARSE_PART;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Number of allocation sites that are listed in a summary.
+const TOP_SITES: usize = 5;
+
+/// Frames of the standard library and the allocator, which are skipped when locating an allocation.
+const INTERNAL_FRAMES: [&str; 7] = [
+    "[root]",
+    "__rg_",
+    "alloc::",
+    "core::",
+    "std::",
+    "hashbrown::",
+    "dhat::",
+];
+
+/// Path of the report that DHAT writes for a part, relative to the repository root.
+pub fn report_path(part: u8) -> String {
+    match part {
+        PARSE_PART => "dhat-heap-parse.json".into(),
+        part => format!("dhat-heap-part-{part}.json"),
+    }
+}
+
+/// The code that allocated memory, with the totals of all its allocations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AllocationSite {
+    /// The function that allocated, e.g. `2024_01::part_one`.
+    pub function: String,
+    /// The source line of the allocation, e.g. `src/bin/2024_01.rs:12:5`.
+    pub location: String,
+    pub bytes: u64,
+    pub blocks: u64,
+}
+
+/// Summary of a DHAT heap report.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeapReport {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    /// Bytes that were live at the global peak.
+    pub max_bytes: u64,
+    pub max_blocks: u64,
+    /// Allocation sites, ordered by the bytes they allocated.
+    pub sites: Vec<AllocationSite>,
+}
+
+impl HeapReport {
+    pub fn read_from_file(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        HeapReport::from_str(&contents)
+    }
+
+    /// Print the totals and the sites that allocated the most bytes.
+    pub fn print(&self, label: &str) {
+        println!(
+            "{ANSI_BOLD}{label}:{ANSI_RESET} {} in {} blocks, max live {} in {} blocks",
+            format_bytes(self.total_bytes),
+            self.total_blocks,
+            format_bytes(self.max_bytes),
+            self.max_blocks
+        );
+
+        for site in self.sites.iter().take(TOP_SITES) {
+            println!(
+                "  {:>10} in {:>6} blocks  {} {ANSI_ITALIC}{}{ANSI_RESET}",
+                format_bytes(site.bytes),
+                site.blocks,
+                site.location,
+                site.function
+            );
+        }
+
+        if self.sites.len() > TOP_SITES {
+            println!("  ... and {} more sites", self.sites.len() - TOP_SITES);
+        }
+    }
+}
+
+/// Splits a frame like `0x10fc97984: 2024_01::part_one (src/bin/2024_01.rs:12:5)` into its function and location.
+fn parse_frame(frame: &str) -> (String, String) {
+    let symbol = frame.split_once(": ").map_or(frame, |(_, symbol)| symbol);
+
+    match symbol.rsplit_once(" (") {
+        Some((function, location)) => (
+            function.into(),
+            location.strip_suffix(')').unwrap_or(location).into(),
+        ),
+        None => (symbol.into(), "???".into()),
+    }
+}
+
+fn is_internal(function: &str) -> bool {
+    let function = function.trim_start_matches('<');
+    // generic impls like `<T as alloc::slice::ConvertVec>::to_vec` belong to the trait's crate.
+    let function = match function.split_once(" as ") {
+        Some((ty, tr)) if !ty.contains("::") => tr,
+        _ => function,
+    };
+    INTERNAL_FRAMES
+        .iter()
+        .any(|prefix| function.starts_with(prefix))
+}
+
+impl FromStr for HeapReport {
+    type Err = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("DHAT report is not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected DHAT report to be a JSON object.")?;
+
+        let frames: Vec<&String> = json
+            .get("ftbl")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected DHAT report to have a frame table `ftbl`.")?
+            .iter()
+            .map(|frame| {
+                frame
+                    .get::<String>()
+                    .ok_or("Expected frames to be strings.")
+            })
+            .collect::<Result<_, _>>()?;
+
+        let program_points = json
+            .get("pps")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected DHAT report to have program points `pps`.")?;
+
+        let mut report = HeapReport::default();
+        // sites by location, as different backtraces can lead to the same allocation.
+        let mut sites: HashMap<String, AllocationSite> = HashMap::new();
+
+        for pp in program_points {
+            let pp = pp
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected program points to be JSON objects.")?;
+
+            let get = |key: &str| {
+                pp.get(key)
+                    .and_then(|v| v.get::<f64>())
+                    .map(|v| *v as u64)
+                    .ok_or(format!("Expected program points to have a number `{key}`."))
+            };
+
+            let (bytes, blocks) = (get("tb")?, get("tbk")?);
+            report.total_bytes += bytes;
+            report.total_blocks += blocks;
+            // ad hoc reports do not track live memory.
+            report.max_bytes += get("gb").unwrap_or(0);
+            report.max_blocks += get("gbk").unwrap_or(0);
+
+            let pp_frames: Vec<(String, String)> = pp
+                .get("fs")
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .ok_or("Expected program points to have frames `fs`.")?
+                .iter()
+                .filter_map(|i| frames.get(*i.get::<f64>()? as usize))
+                .map(|frame| parse_frame(frame))
+                .collect();
+
+            let (function, location) = pp_frames
+                .iter()
+                .find(|(function, _)| !is_internal(function))
+                .or(pp_frames.first())
+                .cloned()
+                .unwrap_or_else(|| ("[unknown]".into(), "???".into()));
+
+            let site = sites
+                .entry(location.clone())
+                .or_insert_with(|| AllocationSite {
+                    function,
+                    location,
+                    bytes: 0,
+                    blocks: 0,
+                });
+            site.bytes += bytes;
+            site.blocks += blocks;
+        }
+
+        report.sites = sites.into_values().collect();
+        report.sites.sort_unstable_by(|a, b| {
+            b.bytes
+                .cmp(&a.bytes)
+                .then_with(|| a.location.cmp(&b.location))
+        });
+
+        Ok(report)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_frame, report_path, HeapReport};
+    use std::str::FromStr;
+
+    const REPORT: &str = r#"{
+        "dhatFileVersion": 2, "mode": "rust-heap", "verb": "Allocated", "bklt": true, "bkacc": false,
+        "tu": "µs", "Mtu": "s", "tuth": 10, "cmd": "target/dhat/2024_01", "pid": 1, "tg": 10, "te": 20,
+        "pps": [
+            { "tb": 64, "tbk": 2, "tl": 5, "mb": 64, "mbk": 2, "gb": 32, "gbk": 1, "eb": 0, "ebk": 0, "fs": [1, 2, 3] },
+            { "tb": 200, "tbk": 1, "tl": 5, "mb": 200, "mbk": 1, "gb": 200, "gbk": 1, "eb": 0, "ebk": 0, "fs": [1, 4] },
+            { "tb": 16, "tbk": 4, "tl": 5, "mb": 16, "mbk": 4, "gb": 0, "gbk": 0, "eb": 0, "ebk": 0, "fs": [1, 5, 6, 3] }
+        ],
+        "ftbl": [
+            "[root]",
+            "0x1: alloc::raw_vec::RawVec<T,A>::grow_one (src/raw_vec.rs:1:1)",
+            "0x2: <alloc::vec::Vec<T> as core::clone::Clone>::clone (src/vec/mod.rs:2:2)",
+            "0x3: 2024_01::part_one (src/bin/2024_01.rs:12:5)",
+            "0x4: advent_of_code::parse::ints (crate/src/parse.rs:107:16)",
+            "0x5: alloc::vec::Vec<T,A>::push (src/vec/mod.rs:3:3)",
+            "0x6: <T as alloc::slice::<impl [T]>::to_vec_in::ConvertVec>::to_vec (alloc/src/slice.rs:4:4)"
+        ]
+    }"#;
+
+    #[test]
+    fn summarizes_reports() {
+        let report = HeapReport::from_str(REPORT).unwrap();
+        assert_eq!(report.total_bytes, 280);
+        assert_eq!(report.total_blocks, 7);
+        assert_eq!(report.max_bytes, 232);
+        assert_eq!(report.max_blocks, 2);
+
+        let sites: Vec<_> = report
+            .sites
+            .iter()
+            .map(|s| (s.location.as_str(), s.bytes, s.blocks))
+            .collect();
+        assert_eq!(
+            sites,
+            [
+                ("crate/src/parse.rs:107:16", 200, 1),
+                ("src/bin/2024_01.rs:12:5", 80, 6)
+            ]
+        );
+        assert_eq!(report.sites[1].function, "2024_01::part_one");
+    }
+
+    #[test]
+    fn parses_frames() {
+        assert_eq!(
+            parse_frame("0x3: 2024_01::part_one (src/bin/2024_01.rs:12:5)"),
+            ("2024_01::part_one".into(), "src/bin/2024_01.rs:12:5".into())
+        );
+        assert_eq!(parse_frame("[root]"), ("[root]".into(), "???".into()));
+    }
+
+    #[test]
+    fn names_reports_by_part() {
+        assert_eq!(report_path(0), "dhat-heap-parse.json");
+        assert_eq!(report_path(2), "dhat-heap-part-2.json");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_reports() {
+        HeapReport::from_str(r#"{ "pps": [] }"#).unwrap();
+    }
+}
@@ -0,0 +1,496 @@
+/// An append-only log of benchmark runs, which `cargo time --compare` compares against.
+///
+/// Every run of `cargo time` appends one line to `timings_history.jsonl`, with the commit it ran on,
+/// when it ran and the time of every part. Unlike `timings.json`, earlier runs are never overwritten.
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::protocol::PARSE_PART;
+use crate::template::runner::Statistic;
+use crate::template::timings::Timings;
+use crate::template::{get_data_dir, Day, Year, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+fn get_file_path(year: Year) -> String {
+    format!("{}/timings_history.jsonl", get_data_dir(year))
+}
+
+/// The time of a single part in a run, with `0` standing for the parse step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTime {
+    pub day: Day,
+    pub part: u8,
+    pub nanos: f64,
+}
+
+/// A single run of `cargo time`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    /// The abbreviated hash of the commit that was checked out, if the repository is a git repository.
+    pub commit: Option<String>,
+    /// A name that was given to the run with `--label`.
+    pub label: Option<String>,
+    /// When the run finished, as seconds since the unix epoch.
+    pub recorded_at: u64,
+    pub statistic: Statistic,
+    pub parts: Vec<PartTime>,
+}
+
+impl Run {
+    /// Collect the part times of solved parts. Failed and timed out parts have no time and are skipped.
+    pub fn from_timings(
+        timings: &Timings,
+        statistic: Statistic,
+        commit: Option<String>,
+        label: Option<String>,
+    ) -> Self {
+        let parts = timings
+            .data
+            .iter()
+            .flat_map(|t| {
+                [PARSE_PART, 1, 2].into_iter().filter_map(|part| {
+                    Some(PartTime {
+                        day: t.day,
+                        part,
+                        nanos: t.nanos(part)?,
+                    })
+                })
+            })
+            .collect();
+
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Run {
+            commit,
+            label,
+            recorded_at,
+            statistic,
+            parts,
+        }
+    }
+
+    fn nanos(&self, day: Day, part: u8) -> Option<f64> {
+        self.parts
+            .iter()
+            .find(|p| p.day == day && p.part == part)
+            .map(|p| p.nanos)
+    }
+
+    /// Whether `name` is the label of the run or a prefix of its commit hash.
+    fn is_named(&self, name: &str) -> bool {
+        self.label.as_deref() == Some(name)
+            || self.commit.as_deref().is_some_and(|c| c.starts_with(name))
+    }
+
+    /// Describes the run for the comparison header, e.g. `a1b2c3d (before-refactor)`.
+    fn describe(&self) -> String {
+        let commit = self.commit.as_deref().unwrap_or("unknown commit");
+        match &self.label {
+            Some(label) => format!("{commit} ({label})"),
+            None => commit.into(),
+        }
+    }
+}
+
+/// Returns the abbreviated hash of the commit that is checked out, if git is available.
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8(output.stdout).ok()?;
+    Some(commit.trim().to_string()).filter(|c| !c.is_empty())
+}
+
+/// All recorded runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// Rehydrate the history from its JSON lines file. If not present, returns no runs.
+    /// Lines that cannot be read are skipped with a warning.
+    pub fn read_from_file(year: Year) -> Self {
+        let Ok(contents) = fs::read_to_string(get_file_path(year)) else {
+            return History::default();
+        };
+
+        let runs = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(|(i, line)| match Run::from_str(line) {
+                Ok(run) => Some(run),
+                Err(e) => {
+                    eprintln!("Skipping line {} of the timings history: {e}", i + 1);
+                    None
+                }
+            })
+            .collect();
+
+        History { runs }
+    }
+
+    /// Append a run to the JSON lines file, without rewriting earlier runs.
+    pub fn append(year: Year, run: &Run) -> Result<(), Error> {
+        let json = JsonValue::from(run)
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+
+        fs::create_dir_all(get_data_dir(year))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(get_file_path(year))?;
+
+        writeln!(file, "{json}")
+    }
+
+    /// Returns the latest run with the given label or commit, or the latest run if no name is given.
+    pub fn baseline(&self, name: Option<&str>) -> Option<&Run> {
+        self.runs
+            .iter()
+            .rev()
+            .find(|run| name.is_none_or(|name| run.is_named(name)))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The change of a part's time between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Delta {
+    /// The change in percent, positive if the part got slower.
+    pub fn percent(&self) -> f64 {
+        (self.after - self.before) / self.before * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn exceeds(&self, threshold: Option<f64>) -> bool {
+        threshold.is_some_and(|threshold| self.percent() > threshold)
+    }
+}
+
+/// Compare the parts of `current` with the same parts of `baseline`. Parts missing in either run are skipped.
+pub fn compare(baseline: &Run, current: &Run) -> Vec<Delta> {
+    current
+        .parts
+        .iter()
+        .filter_map(|p| {
+            let before = baseline.nanos(p.day, p.part).filter(|n| *n > 0.0)?;
+            Some(Delta {
+                day: p.day,
+                part: p.part,
+                before,
+                after: p.nanos,
+            })
+        })
+        .collect()
+}
+
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", std::time::Duration::from_secs_f64(nanos / 1e9))
+}
+
+/// Print the deltas of every part against `baseline`.
+/// Returns the number of parts that got slower by more than `threshold` percent.
+pub fn print_comparison(baseline: &Run, current: &Run, threshold: Option<f64>) -> usize {
+    println!(
+        "{ANSI_BOLD}Compared to {}:{ANSI_RESET}",
+        baseline.describe()
+    );
+
+    if baseline.statistic != current.statistic {
+        eprintln!(
+            "Warning: the baseline stored the {}, this run the {}.",
+            baseline.statistic, current.statistic
+        );
+    }
+
+    let deltas = compare(baseline, current);
+
+    if deltas.is_empty() {
+        println!("No parts in common with the baseline.");
+        return 0;
+    }
+
+    for delta in &deltas {
+        let part = match delta.part {
+            PARSE_PART => "Parse ".into(),
+            part => format!("Part {part}"),
+        };
+
+        let percent = delta.percent();
+        let color = if percent > 0.0 { ANSI_RED } else { ANSI_GREEN };
+        let marker = if delta.exceeds(threshold) {
+            " ✖ regression"
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {} {part}: {:>9} → {:>9} {color}{percent:+.1}%{marker}{ANSI_RESET}",
+            delta.day,
+            format_nanos(delta.before),
+            format_nanos(delta.after),
+        );
+    }
+
+    deltas.iter().filter(|d| d.exceeds(threshold)).count()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let optional = |s: &Option<String>| s.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert("commit".into(), optional(&value.commit));
+        map.insert("label".into(), optional(&value.label));
+        map.insert(
+            "recorded_at".into(),
+            JsonValue::Number(value.recorded_at as f64),
+        );
+        map.insert(
+            "statistic".into(),
+            JsonValue::String(value.statistic.as_str().into()),
+        );
+
+        let mut days: HashMap<String, JsonValue> = HashMap::new();
+        for p in &value.parts {
+            let parts = days
+                .entry(p.day.to_string())
+                .or_insert_with(|| JsonValue::Object(HashMap::new()));
+            if let JsonValue::Object(parts) = parts {
+                parts.insert(p.part.to_string(), JsonValue::Number(p.nanos));
+            }
+        }
+        map.insert("days".into(), JsonValue::Object(days));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for Run {
+    type Err = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let optional = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected run.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+
+        let recorded_at = json
+            .get("recorded_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected run.recorded_at to be a number.")?;
+
+        let statistic = json
+            .get("statistic")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected run.statistic to be a string.")?
+            .parse()?;
+
+        let mut parts = vec![];
+
+        for (day, day_parts) in json
+            .get("days")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected run.days to be a JSON object.")?
+        {
+            let day = Day::from_str(day).map_err(|_| format!("Expected `{day}` to be a day."))?;
+
+            for (part, nanos) in day_parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected the days of a run to be JSON objects.")?
+            {
+                parts.push(PartTime {
+                    day,
+                    part: part
+                        .parse()
+                        .map_err(|_| format!("Expected `{part}` to be a part."))?,
+                    nanos: *nanos
+                        .get::<f64>()
+                        .ok_or("Expected part times to be numbers.")?,
+                });
+            }
+        }
+
+        parts.sort_unstable_by_key(|p| (p.day, p.part));
+
+        Ok(Run {
+            commit: optional("commit")?,
+            label: optional("label")?,
+            recorded_at,
+            statistic,
+            parts,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{compare, History, PartTime, Run};
+    use crate::day;
+    use crate::template::runner::Statistic;
+    use crate::template::timings::{Timing, Timings};
+
+    fn run(commit: &str, label: Option<&str>, nanos: [f64; 2]) -> Run {
+        Run {
+            commit: Some(commit.into()),
+            label: label.map(Into::into),
+            recorded_at: 1_733_011_200,
+            statistic: Statistic::Mean,
+            parts: vec![
+                PartTime {
+                    day: day!(1),
+                    part: 1,
+                    nanos: nanos[0],
+                },
+                PartTime {
+                    day: day!(1),
+                    part: 2,
+                    nanos: nanos[1],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn collects_parts_from_timings() {
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(3),
+                part_1: Some("1.5ms".into()),
+                part_2: None,
+                parse: Some("250.0µs".into()),
+                failed: vec![2],
+                timed_out: vec![],
+                allocs: vec![],
+                nanos: vec![],
+                total_nanos: 1_750_000.0,
+                statistic: Statistic::Median,
+            }],
+        };
+
+        let run = Run::from_timings(&timings, Statistic::Median, Some("a1b2c3d".into()), None);
+        let parts: Vec<_> = run.parts.iter().map(|p| (p.part, p.nanos)).collect();
+        assert_eq!(parts, [(0, 250_000.0), (1, 1_500_000.0)]);
+    }
+
+    #[test]
+    fn records_exact_times() {
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(2),
+                part_1: Some("931.0ns".into()),
+                part_2: Some("1.0µs".into()),
+                parse: None,
+                failed: vec![],
+                timed_out: vec![],
+                allocs: vec![],
+                nanos: vec![(1, 930.6), (2, 1004.4)],
+                total_nanos: 1935.0,
+                statistic: Statistic::Mean,
+            }],
+        };
+
+        let run = Run::from_timings(&timings, Statistic::Mean, None, None);
+        let json = JsonValue::from(&run).stringify().unwrap();
+        let parts: Vec<_> = Run::from_str(&json)
+            .unwrap()
+            .parts
+            .iter()
+            .map(|p| (p.part, p.nanos))
+            .collect();
+        assert_eq!(parts, [(1, 930.6), (2, 1004.4)]);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let run = run("a1b2c3d", Some("baseline"), [1000.0, 2_500_000.0]);
+        let json = JsonValue::from(&run).stringify().unwrap();
+        assert!(!json.contains('\n'));
+        assert_eq!(Run::from_str(&json), Ok(run));
+    }
+
+    #[test]
+    fn finds_baselines() {
+        let history = History {
+            runs: vec![
+                run("a1b2c3d", Some("before-refactor"), [100.0, 200.0]),
+                run("e4f5a6b", None, [110.0, 190.0]),
+                run("e4f5a6b", None, [120.0, 180.0]),
+            ],
+        };
+
+        let nanos = |run: Option<&Run>| run.map(|r| r.parts[0].nanos);
+        assert_eq!(nanos(history.baseline(None)), Some(120.0));
+        assert_eq!(
+            nanos(history.baseline(Some("before-refactor"))),
+            Some(100.0)
+        );
+        assert_eq!(nanos(history.baseline(Some("a1b2"))), Some(100.0));
+        assert_eq!(nanos(history.baseline(Some("e4f5a6b"))), Some(120.0));
+        assert_eq!(nanos(history.baseline(Some("main"))), None);
+    }
+
+    #[test]
+    fn compares_runs() {
+        let mut current = run("e4f5a6b", None, [150.0, 150.0]);
+        current.parts.push(PartTime {
+            day: day!(2),
+            part: 1,
+            nanos: 10.0,
+        });
+
+        let deltas = compare(&run("a1b2c3d", None, [100.0, 200.0]), &current);
+        assert_eq!(deltas.len(), 2);
+        assert!((deltas[0].percent() - 50.0).abs() < 1e-9);
+        assert!((deltas[1].percent() + 25.0).abs() < 1e-9);
+        assert!(deltas[0].exceeds(Some(10.0)));
+        assert!(!deltas[0].exceeds(Some(50.0)));
+        assert!(!deltas[0].exceeds(None));
+        assert!(!deltas[1].exceeds(Some(0.0)));
+    }
+}
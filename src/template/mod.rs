@@ -1,44 +1,102 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use day_selection::*;
+pub use year::*;
 
+mod answers;
+mod benchmark_chart;
 mod day;
+mod day_selection;
+mod heap_report;
+mod history;
+mod protocol;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, day: YearDay) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join("data")
+        .join(day.year.to_string())
+        .join(folder)
+        .join(format!("{}.txt", day.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, day: YearDay, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
+        .join(day.year.to_string())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", day.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Directory that holds the inputs, examples and stored results of a year.
+pub(crate) fn get_data_dir(year: Year) -> String {
+    format!("data/{year}")
+}
+
+pub(crate) fn get_input_path(day: YearDay) -> String {
+    format!("data/{}/inputs/{}.txt", day.year, day.day)
+}
+
+pub(crate) fn get_example_path(day: YearDay) -> String {
+    format!("data/{}/examples/{}.txt", day.year, day.day)
+}
+
+pub(crate) fn get_puzzle_path(day: YearDay) -> String {
+    format!("data/{}/puzzles/{}.md", day.year, day.day)
+}
+
+pub(crate) fn get_bin_path(day: YearDay) -> String {
+    format!("src/bin/{day}.rs")
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// The year of `DAY` is read from the solution's file name, which has to be `src/bin/<year>_<day>.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Parts can return an `Option` or a `Result` whose error implements `Display`.
+/// `None` marks a part as not solved yet, while errors are printed in place of the answer and the part is reported as failed.
+///
+/// A `parse = <function>` parameter turns the puzzle input into a shared value that is passed to every part by reference.
+/// The parse step is timed separately from the parts.
+/// If the parse function returns a `Result`, use `try_parse = <function>` instead. The parts are skipped if it returns an error:
+///
+/// ```ignore
+/// advent_of_code::solution!(5, parse = parse_input);
+/// advent_of_code::solution!(5, parse = parse_input, 2);
+/// advent_of_code::solution!(5, try_parse = parse_input);
+/// ```
+///
+/// Also creates the constant `SOLUTION`, which the main binary uses to run the day in-process when built with the `registry` feature.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +108,70 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@infallible $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@infallible $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@infallible $day, $parse, [part_two, 2]);
+    };
+    ($day:expr, try_parse = $parse:expr) => {
+        $crate::solution!(@parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, try_parse = $parse:expr, 1) => {
+        $crate::solution!(@parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, try_parse = $parse:expr, 2) => {
+        $crate::solution!(@parsed $day, $parse, [part_two, 2]);
+    };
 
+    // inputs and parsed values are leaked, so that parts that time out can be abandoned on another thread.
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        $crate::solution!(@setup $day, |options| {
+            use $crate::template::runner::*;
+            let input: &'static str = $crate::template::read_file("inputs", DAY).leak();
+            $( run_part($func, input, DAY, $part, options); )*
+        });
+    };
+
+    (@infallible $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(
+            @parsed $day,
+            |input: &str| Ok::<_, std::convert::Infallible>($parse(input)),
+            $( [$func, $part] )*
+        );
+    };
+
+    (@parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day, |options| {
+            use $crate::template::runner::*;
+            let input: &'static str = $crate::template::read_file("inputs", DAY).leak();
+            if let Some(parsed) = run_parse($parse, input, DAY, options) {
+                let parsed: &'static _ = Box::leak(Box::new(parsed));
+                $( run_part($func, parsed, DAY, $part, options); )*
+            }
+        });
+    };
+
+    (@setup $day:expr, $run:expr) => {
+        /// The current day. The year is read from the name of the solution file, e.g. `2024_01.rs`.
+        const DAY: $crate::template::YearDay =
+            $crate::template::YearDay::__from_path(file!(), $crate::day!($day));
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// Entry of the current day in the in-process solution registry.
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution::new(DAY, $run);
+
+        // unused when the solution is compiled into the registry.
+        #[allow(dead_code)]
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            (SOLUTION.run)(&$crate::template::runner::RunOptions::from_args());
         }
     };
 }
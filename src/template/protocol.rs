@@ -0,0 +1,386 @@
+/// Machine-readable result records that solution binaries report back to the runner.
+///
+/// When the environment variable `AOC_RESULT_PATH` is set, every part run by a solution binary
+/// appends one JSON object per line to the file at that path. `run_multi` reads these records
+/// instead of scraping the human-readable output.
+/// Solutions that run in-process report their records through [`capture_records`] instead.
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc::AllocStats;
+use crate::template::runner::{BenchStats, Statistic};
+use crate::template::Day;
+
+pub const RESULT_PATH_ENV: &str = "AOC_RESULT_PATH";
+
+/// Part number used for the records of a solution's shared parse step.
+pub const PARSE_PART: u8 = 0;
+
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<PartRecord>>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, collecting the records emitted while it runs instead of writing them to a file.
+pub fn capture_records(f: impl FnOnce()) -> Vec<PartRecord> {
+    CAPTURED.with_borrow_mut(|captured| *captured = Some(vec![]));
+    f();
+    CAPTURED.with_borrow_mut(Option::take).unwrap_or_default()
+}
+
+/// Outcome of running a single part of a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an error or panicked.
+    Failed,
+    /// The part exceeded its time limit.
+    TimedOut,
+}
+
+impl PartStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Failed => "failed",
+            PartStatus::TimedOut => "timed_out",
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "failed" => Ok(PartStatus::Failed),
+            "timed_out" => Ok(PartStatus::TimedOut),
+            x => Err(format!("unknown part status `{x}`.")),
+        }
+    }
+}
+
+/// Result of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    /// The part number, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Duration of the part. For benched parts, this is the value of `statistic`.
+    pub nanos: f64,
+    pub samples: u128,
+    pub statistic: Statistic,
+    /// Summary of all samples, present if the part was benched.
+    pub stats: Option<BenchStats>,
+    pub status: PartStatus,
+    /// Allocations of the part, present if the solution was built with the `alloc-stats` feature.
+    pub allocs: Option<AllocStats>,
+}
+
+impl PartRecord {
+    /// Hand this record to an active [`capture_records`] call, or append it to the file named by
+    /// `AOC_RESULT_PATH`. Does nothing if neither is present.
+    pub fn emit(&self) -> io::Result<()> {
+        let is_captured = CAPTURED.with_borrow_mut(|captured| match captured {
+            Some(records) => {
+                records.push(self.clone());
+                true
+            }
+            None => false,
+        });
+
+        if is_captured {
+            return Ok(());
+        }
+
+        let Some(path) = env::var_os(RESULT_PATH_ENV) else {
+            return Ok(());
+        };
+
+        let line = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+
+        writeln!(file, "{line}")
+    }
+}
+
+/// Read all records from a result file written by [`PartRecord::emit`].
+pub fn read_records(path: &Path) -> Result<Vec<PartRecord>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse_records(&contents)
+}
+
+fn parse_records(contents: &str) -> Result<Vec<PartRecord>, String> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("result record is not valid JSON."))?;
+            PartRecord::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "statistic".into(),
+            JsonValue::String(value.statistic.as_str().into()),
+        );
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(stats) => stats_to_json(stats),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "allocs".into(),
+            match &value.allocs {
+                Some(allocs) => allocs.into(),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let statistic = json
+            .get("statistic")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.statistic to be a string.")?
+            .parse()?;
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(stats_from_json(v)?),
+            _ => None,
+        };
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        // records of solutions built without the `alloc-stats` feature have no allocations.
+        let allocs = match json.get("allocs") {
+            Some(v) if !v.is_null() => Some(AllocStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartRecord {
+            day,
+            part,
+            answer: answer.cloned(),
+            nanos,
+            samples,
+            statistic,
+            stats,
+            status,
+            allocs,
+        })
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn stats_to_json(stats: &BenchStats) -> JsonValue {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+    for (key, value) in [
+        ("min", stats.min),
+        ("median", stats.median),
+        ("p95", stats.p95),
+        ("mean", stats.mean),
+        ("stddev", stats.stddev),
+    ] {
+        map.insert(key.into(), JsonValue::Number(value.as_nanos() as f64));
+    }
+
+    map.insert("samples".into(), JsonValue::Number(stats.samples as f64));
+
+    JsonValue::Object(map)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn stats_from_json(value: &JsonValue) -> Result<BenchStats, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected record.stats to be null or a JSON object.")?;
+
+    let get = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or(format!("Expected record.stats.{key} to be a number."))
+    };
+
+    Ok(BenchStats {
+        min: Duration::from_nanos(get("min")?),
+        median: Duration::from_nanos(get("median")?),
+        p95: Duration::from_nanos(get("p95")?),
+        mean: Duration::from_nanos(get("mean")?),
+        stddev: Duration::from_nanos(get("stddev")?),
+        samples: u128::from(get("samples")?),
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_records, PartRecord, PartStatus};
+    use crate::day;
+    use crate::template::alloc::AllocStats;
+    use crate::template::runner::{BenchStats, Statistic};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_records() {
+        let record = PartRecord {
+            day: day!(3),
+            part: 2,
+            answer: Some("42 (1.0ns @ 10 samples)".into()),
+            nanos: 74.0,
+            samples: 10000,
+            statistic: Statistic::Median,
+            stats: Some(BenchStats {
+                min: Duration::from_nanos(70),
+                median: Duration::from_nanos(74),
+                p95: Duration::from_nanos(90),
+                mean: Duration::from_nanos(75),
+                stddev: Duration::from_nanos(3),
+                samples: 10000,
+            }),
+            status: PartStatus::Solved,
+            allocs: Some(AllocStats {
+                allocations: 3,
+                bytes: 2048,
+                peak_bytes: 1024,
+            }),
+        };
+        let line = JsonValue::from(&record).stringify().unwrap();
+        let parsed = parse_records(&format!("{line}\n")).unwrap();
+        assert_eq!(parsed, vec![record]);
+    }
+
+    #[test]
+    fn handles_multiline_answers() {
+        let record = PartRecord {
+            day: day!(1),
+            part: 1,
+            answer: Some("###\n#.#\n###".into()),
+            nanos: 1.0,
+            samples: 1,
+            statistic: Statistic::Mean,
+            stats: None,
+            status: PartStatus::Solved,
+            allocs: None,
+        };
+        let line = JsonValue::from(&record).stringify().unwrap();
+        assert_eq!(line.lines().count(), 1);
+        assert_eq!(parse_records(&line).unwrap(), vec![record]);
+    }
+
+    #[test]
+    fn handles_unsolved_parts() {
+        let line = r#"{ "day": "01", "part": 2, "answer": null, "nanos": 0, "samples": 1, "statistic": "mean", "stats": null, "status": "unsolved" }"#;
+        let parsed = parse_records(line).unwrap();
+        assert_eq!(parsed[0].answer, None);
+        assert_eq!(parsed[0].status, PartStatus::Unsolved);
+        assert_eq!(parsed[0].allocs, None);
+    }
+
+    #[test]
+    fn handles_failed_parts() {
+        let line = r#"{ "day": "05", "part": 0, "answer": null, "nanos": 10, "samples": 1, "statistic": "mean", "stats": null, "status": "failed" }"#;
+        let parsed = parse_records(line).unwrap();
+        assert_eq!(parsed[0].part, 0);
+        assert_eq!(parsed[0].status, PartStatus::Failed);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_records() {
+        parse_records(r#"{ "day": "01", "part": 1 }"#).unwrap();
+    }
+}
@@ -0,0 +1,212 @@
+//! Extracts example inputs and answers from puzzle descriptions downloaded by aoc-cli.
+//!
+//! Puzzle markdown marks example inputs as fenced code blocks, usually introduced by a paragraph
+//! containing "example", and emphasizes answers (e.g. `` `*11*` ``). Both are heuristics: the output
+//! is meant as a proposal that should be checked against the puzzle text.
+
+/// Examples and the proposed example answer for one part of a puzzle.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzlePart {
+    /// Code blocks that look like example inputs, in order of appearance.
+    pub examples: Vec<String>,
+    /// The last emphasized value of the part, which usually is the answer for the example.
+    pub answer: Option<String>,
+}
+
+/// Parse a puzzle description into its parts. The second part is only present once unlocked.
+pub fn parse_puzzle(markdown: &str) -> Vec<PuzzlePart> {
+    split_parts(markdown)
+        .into_iter()
+        .map(|section| PuzzlePart {
+            examples: find_examples(section),
+            answer: find_emphasized(section).pop(),
+        })
+        .collect()
+}
+
+fn split_parts(markdown: &str) -> Vec<&str> {
+    let part_two = markdown.match_indices('\n').map(|(i, _)| i + 1).find(|i| {
+        let line = markdown[*i..].lines().next().unwrap_or_default();
+        line.contains("--- Part Two ---")
+    });
+
+    match part_two {
+        Some(i) => vec![&markdown[..i], &markdown[i..]],
+        None => vec![markdown],
+    }
+}
+
+/// A fenced code block and the paragraph preceding it.
+struct CodeBlock {
+    preceding: String,
+    contents: String,
+}
+
+fn find_code_blocks(section: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut paragraph_ended = false;
+    let mut code: Option<Vec<&str>> = None;
+
+    for line in section.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+
+        match (&mut code, is_fence) {
+            (None, true) => code = Some(vec![]),
+            (Some(lines), true) => {
+                blocks.push(CodeBlock {
+                    preceding: paragraph.join(" "),
+                    contents: lines.join("\n"),
+                });
+                code = None;
+                paragraph.clear();
+                paragraph_ended = false;
+            }
+            (Some(lines), false) => lines.push(line),
+            (None, false) if line.trim().is_empty() => paragraph_ended = true,
+            (None, false) => {
+                // only keep the paragraph directly preceding a block.
+                if paragraph_ended {
+                    paragraph.clear();
+                    paragraph_ended = false;
+                }
+                paragraph.push(line);
+            }
+        }
+    }
+
+    blocks
+}
+
+fn find_examples(section: &str) -> Vec<String> {
+    let blocks = find_code_blocks(section);
+
+    let mut examples: Vec<String> = blocks
+        .iter()
+        .filter(|b| b.preceding.to_lowercase().contains("example"))
+        .map(|b| b.contents.clone())
+        .collect();
+
+    // fall back to the first multi-line block if no block is introduced as an example.
+    if examples.is_empty() {
+        examples.extend(
+            blocks
+                .iter()
+                .find(|b| b.contents.lines().count() > 1)
+                .map(|b| b.contents.clone()),
+        );
+    }
+
+    examples.dedup();
+    examples
+}
+
+/// Find emphasized values, e.g. `` `*11*` ``, `*`11`*` or `**11**`.
+fn find_emphasized(section: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut in_code_block = false;
+
+    for line in section.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        // solved puzzles also emphasize the submitted answer, which is not the example's answer.
+        if in_code_block || line.starts_with("Your puzzle answer was") {
+            continue;
+        }
+
+        let mut rest = line;
+
+        while let Some(start) = rest.find('*') {
+            let after_stars = rest[start..].trim_start_matches('*');
+            let stars = rest[start..].len() - after_stars.len();
+            let closing = "*".repeat(stars);
+
+            let Some(end) = after_stars.find(&closing) else {
+                break;
+            };
+
+            let value = after_stars[..end].trim_matches('`').trim();
+            if !value.is_empty() && !value.contains(' ') {
+                values.push(value.to_string());
+            }
+
+            rest = &after_stars[end + stars..];
+        }
+    }
+
+    values
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_puzzle;
+
+    const PUZZLE: &str = "\\--- Day 1: Historian Hysteria ---
+----------
+
+The Chief Historian is always present.
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Maybe the lists are only off by a small amount! To find out, pair up the numbers.
+
+```
+1 3
+```
+
+In the example above, this is `2 + 1`, a total distance of `*11*`!
+
+Your puzzle answer was `*1834060*`.
+
+\\--- Part Two ---
+----------
+
+This time, you'll need to figure out exactly how often each number appears.
+
+Here is a new example:
+
+```
+xmul(2,4)
+```
+
+So, for these example lists, the similarity score at the end of this process is `*31*`.
+";
+
+    #[test]
+    fn splits_parts() {
+        assert_eq!(parse_puzzle(PUZZLE).len(), 2);
+        let parts = parse_puzzle(&PUZZLE.replace("\\--- Part Two", "## \\--- Part Two"));
+        assert_eq!(parts.len(), 2);
+    }
+
+    #[test]
+    fn extracts_examples() {
+        let parts = parse_puzzle(PUZZLE);
+        assert_eq!(parts[0].examples, vec!["3   4\n4   3".to_string()]);
+        assert_eq!(parts[1].examples, vec!["xmul(2,4)".to_string()]);
+    }
+
+    #[test]
+    fn extracts_answers() {
+        let parts = parse_puzzle(PUZZLE);
+        assert_eq!(parts[0].answer, Some("11".to_string()));
+        assert_eq!(parts[1].answer, Some("31".to_string()));
+    }
+
+    #[test]
+    fn handles_locked_part_two() {
+        let (part_one, _) = PUZZLE.split_once("\\--- Part Two").unwrap();
+        let parts = parse_puzzle(part_one);
+        assert_eq!(parts.len(), 1);
+    }
+}
@@ -2,8 +2,11 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::alloc::{format_bytes, AllocStats};
+use crate::template::protocol::PARSE_PART;
+use crate::template::runner::Statistic;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{get_bin_path, Year, YearDay};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -25,11 +28,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -52,47 +50,172 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Describes which statistic the table shows. Means are the default and are not called out.
+fn describe_statistics(timings: &Timings) -> Option<String> {
+    let mut statistics: Vec<Statistic> = vec![];
+
+    for timing in &timings.data {
+        if !statistics.contains(&timing.statistic) {
+            statistics.push(timing.statistic);
+        }
+    }
+
+    if statistics.iter().all(|s| *s == Statistic::Mean) {
+        return None;
+    }
+
+    let names: Vec<&str> = statistics.iter().map(|s| s.as_str()).collect();
+    Some(format!(
+        "_Times show the {} of each benchmark._",
+        names.join(" / ")
+    ))
+}
+
+/// Formats the time of a part for the table. Parts without a time failed, timed out or are not solved.
+fn format_cell(timing: &Timing, part: u8, time: Option<&str>) -> String {
+    match time {
+        Some(time) => time.into(),
+        None if timing.failed.contains(&part) => "failed".into(),
+        None if timing.timed_out.contains(&part) => "timed out".into(),
+        None => "-".into(),
+    }
+}
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+/// Formats the allocations of a part for the table, e.g. `12 / 4.0 KiB / 1.0 KiB`.
+fn format_allocs_cell(allocs: Option<&AllocStats>) -> String {
+    match allocs {
+        Some(allocs) => format!(
+            "{} / {} / {}",
+            allocs.allocations,
+            format_bytes(allocs.bytes),
+            format_bytes(allocs.peak_bytes)
+        ),
+        None => "-".into(),
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    chart: Option<&str>,
+) -> String {
+    let header = format!("{prefix} Benchmarks ({year})");
+    let statistics = describe_statistics(&timings);
+    // only show the parse column if at least one solution has a separate parse step.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    // only show allocation columns if at least one solution was run with the counting allocator.
+    let has_allocs = timings.data.iter().any(|t| !t.allocs.is_empty());
+
+    let parts: &[u8] = if has_parse {
+        &[PARSE_PART, 1, 2]
+    } else {
+        &[1, 2]
+    };
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header.clone(), String::new()];
+
+    if let Some(chart) = chart {
+        lines.push(format!(
+            "![{}](./{chart})",
+            header.trim_start_matches(['#', ' '])
+        ));
+        lines.push(String::new());
+    }
+
+    let (mut columns, mut alignments) = if has_parse {
+        (
+            "| Day | Parse | Part 1 | Part 2 |".to_string(),
+            "| :---: | :---: | :---: | :---:  |".to_string(),
+        )
+    } else {
+        (
+            "| Day | Part 1 | Part 2 |".to_string(),
+            "| :---: | :---: | :---:  |".to_string(),
+        )
+    };
+
+    if has_allocs {
+        for part in parts {
+            match *part {
+                PARSE_PART => columns.push_str(" Parse allocs |"),
+                part => columns.push_str(&format!(" Part {part} allocs |")),
+            }
+            alignments.push_str(" :---: |");
+        }
+    }
+
+    lines.push(columns);
+    lines.push(alignments);
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = format!("./{}", get_bin_path(YearDay::new(year, timing.day)));
+        let parse = if has_parse {
+            format!(
+                " `{}` |",
+                format_cell(&timing, PARSE_PART, timing.parse.as_deref())
+            )
+        } else {
+            String::new()
+        };
+        let allocs: String = if has_allocs {
+            parts
+                .iter()
+                .map(|part| format!(" `{}` |", format_allocs_cell(timing.allocs(*part))))
+                .collect()
+        } else {
+            String::new()
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{} `{}` | `{}` |{}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            parse,
+            format_cell(&timing, 1, timing.part_1.as_deref()),
+            format_cell(&timing, 2, timing.part_2.as_deref()),
+            allocs
         ));
     }
 
     lines.push(String::new());
+    if let Some(statistics) = statistics {
+        lines.push(statistics);
+        lines.push(String::new());
+    }
+    if has_allocs {
+        lines.push(
+            "_Allocations show the number of allocations / bytes allocated / peak memory of each part._"
+                .into(),
+        );
+        lines.push(String::new());
+    }
     lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    chart: Option<&str>,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", year, timings, total_millis, chart);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Replace the benchmark table in the readme with the timings of `year`.
+/// If `chart` is the path of a benchmark chart, the chart is embedded above the table.
+pub fn update(year: Year, timings: Timings, chart: Option<&str>) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis, chart)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,7 +223,10 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::alloc::AllocStats, template::runner::Statistic, template::timings::Timing,
+        template::timings::Timings, year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,19 +235,37 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 3e+10,
+                    statistic: Statistic::Mean,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 7e+10,
+                    statistic: Statistic::Mean,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 9e+10,
+                    statistic: Statistic::Mean,
                 },
             ],
         }
@@ -131,28 +275,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0, None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0, None).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0, None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +304,18 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0, None).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## Benchmarks (2024)",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2024_01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2024_02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2024_04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +324,75 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmark_statistics() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        for timing in &mut timings.data {
+            timing.statistic = Statistic::Median;
+        }
+        update_content(&mut s, year!(2024), timings, 190.0, None).unwrap();
+        assert!(s.contains("\n\n_Times show the median of each benchmark._\n\n**Total: 190.00ms**"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_column() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+        update_content(&mut s, year!(2024), timings, 190.0, None).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2024_01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2024_02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_failed_parts() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].failed = vec![2];
+        update_content(&mut s, year!(2024), timings, 190.0, None).unwrap();
+        assert!(s.contains("| [Day 4](./src/bin/2024_04.rs) | `40ms` | `failed` |"));
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1 = None;
+        timings.data[0].timed_out = vec![1];
+        update_content(&mut s, year!(2024), timings, 190.0, None).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/2024_01.rs) | `timed out` | `20ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_allocs() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[0].allocs = vec![(
+            1,
+            AllocStats {
+                allocations: 12,
+                bytes: 4096,
+                peak_bytes: 1536,
+            },
+        )];
+        update_content(&mut s, year!(2024), timings, 190.0, None).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 allocs | Part 2 allocs |"));
+        assert!(s.contains("| :---: | :---: | :---:  | :---: | :---: |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2024_01.rs) | `10ms` | `20ms` | `12 / 4.0 KiB / 1.5 KiB` | `-` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/2024_02.rs) | `30ms` | `40ms` | `-` | `-` |"));
+        assert!(s.contains("_Allocations show the number of allocations"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_chart() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let chart = Some(".assets/benchmarks-2024.svg");
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0, chart).unwrap();
+        assert!(s.contains(
+            "## Benchmarks (2024)\n\n![Benchmarks (2024)](./.assets/benchmarks-2024.svg)\n\n| Day |"
+        ));
+    }
 }
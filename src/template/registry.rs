@@ -0,0 +1,34 @@
+/// In-process registry of solutions.
+/// Lets `all` and `time` run every scaffolded day from the main binary instead of spawning one `cargo run` per day.
+use crate::template::runner::RunOptions;
+use crate::template::YearDay;
+
+/// Entry point of a single day's solution, as generated by the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: YearDay,
+    pub run: fn(&RunOptions),
+}
+
+impl Solution {
+    pub const fn new(day: YearDay, run: fn(&RunOptions)) -> Self {
+        Self { day, run }
+    }
+}
+
+/// A set of solutions that can be called directly.
+#[derive(Clone, Default)]
+pub struct Registry {
+    solutions: Vec<Solution>,
+}
+
+impl Registry {
+    pub fn new(solutions: Vec<Solution>) -> Self {
+        Self { solutions }
+    }
+
+    /// Returns the solution for `day` if it has been registered.
+    pub fn get(&self, day: YearDay) -> Option<&Solution> {
+        self.solutions.iter().find(|s| s.day == day)
+    }
+}
@@ -1,19 +1,36 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::protocol::PARSE_PART;
+use crate::template::runner::{BenchConfig, Timeouts};
+use crate::template::{
+    alloc, registry::Registry, Day, Year, YearDay, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Run the solutions for a set of days of `year`.
+/// Days present in `registry` are run in-process, all others are run through their binary.
+/// If `bench` is set, every part is benched with it and the resulting timings are returned.
+/// Parts that exceed `timeouts` are abandoned, and the remaining parts and days still run.
+/// With `allocs`, solutions are built with the `alloc-stats` feature and report their allocations.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    bench: Option<&BenchConfig>,
+    timeouts: &Timeouts,
+    allocs: bool,
+    registry: Option<&Registry>,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    all_days(year)
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
             if need_space {
@@ -24,17 +41,36 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = run_day(
+                YearDay::new(year, day),
+                is_release,
+                bench,
+                timeouts,
+                allocs,
+                registry,
+            );
 
-            if output.is_empty() {
+            if output.timed_out {
+                println!("Stopped after exceeding the time limit of the day.");
+            }
+
+            if !output.records.is_empty() || output.timed_out {
+                let val = child_commands::timing_from_output(&output, day);
+                timings.push(val);
+            } else if output.stdout.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                // binaries built from an older template do not report structured results.
+                let val = child_commands::parse_exec_time(&output.stdout, day);
                 timings.push(val);
             }
         });
 
-    if is_timed {
+    if let Some(failures) = describe_failures(&timings) {
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET} {failures}");
+    }
+
+    if bench.is_some() {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -46,6 +82,49 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Lists the parts that failed or timed out, e.g. `day 03 (part 1), day 05 (parse, timed out)`.
+fn describe_failures(timings: &[Timing]) -> Option<String> {
+    let describe = |timing: &Timing, part: u8, suffix: &str| match part {
+        PARSE_PART => format!("day {} (parse{suffix})", timing.day),
+        part => format!("day {} (part {part}{suffix})", timing.day),
+    };
+
+    let failures: Vec<String> = timings
+        .iter()
+        .flat_map(|timing| {
+            let failed = timing.failed.iter().map(|part| describe(timing, *part, ""));
+            let timed_out = timing
+                .timed_out
+                .iter()
+                .map(|part| describe(timing, *part, ", timed out"));
+            failed.chain(timed_out)
+        })
+        .collect();
+
+    (!failures.is_empty()).then(|| failures.join(", "))
+}
+
+/// Run the solution for a single day, in-process if it is present in `registry`.
+/// If `allocs` is set but the main binary does not count allocations, the solution's binary is run instead.
+/// With time limits, the solution's binary is run as well, so that a part that times out can be stopped.
+pub fn run_day(
+    day: YearDay,
+    is_release: bool,
+    bench: Option<&BenchConfig>,
+    timeouts: &Timeouts,
+    allocs: bool,
+    registry: Option<&Registry>,
+) -> child_commands::ChildOutput {
+    let registry = registry
+        .filter(|_| alloc::ENABLED || !allocs)
+        .filter(|_| !timeouts.is_limited());
+
+    match registry.and_then(|r| r.get(day)) {
+        Some(solution) => in_process::run_solution(solution, bench, timeouts),
+        None => child_commands::run_solution(day, bench, timeouts, allocs, is_release).unwrap(),
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,41 +138,158 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Solutions compiled into the main binary with the `registry` feature.
+/// These are called directly and report their results without spawning a child process.
+pub mod in_process {
+    use super::child_commands::ChildOutput;
+    use crate::template::protocol::capture_records;
+    use crate::template::registry::Solution;
+    use crate::template::runner::{catch_panic, BenchConfig, RunOptions, Statistic, Timeouts};
+
+    /// Run a registered solution in the current process.
+    /// A solution that panics outside of its parts, e.g. because its input is missing, is reported and skipped like a bin that crashes.
+    /// Parts that time out could not be stopped here, so [`super::run_day`] only runs solutions in-process without time limits.
+    pub fn run_solution(
+        solution: &Solution,
+        bench: Option<&BenchConfig>,
+        timeouts: &Timeouts,
+    ) -> ChildOutput {
+        let options = RunOptions {
+            time: bench.is_some(),
+            submit: None,
+            statistic: Statistic::from_env(),
+            bench: bench.copied().unwrap_or_default(),
+            timeouts: *timeouts,
+            deadline: timeouts.deadline(),
+        };
+
+        let mut result = Ok(());
+        let records = capture_records(|| result = catch_panic(|| (solution.run)(&options)));
+
+        if let Err(e) = result {
+            eprintln!("{e}");
+        }
+
+        ChildOutput {
+            stdout: vec![],
+            records,
+            timed_out: false,
+        }
+    }
+
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::run_solution;
+        use crate::day;
+        use crate::template::registry::Solution;
+        use crate::template::runner::Timeouts;
+        use crate::template::{Year, YearDay};
+
+        #[test]
+        fn skips_solutions_that_panic() {
+            let day = YearDay::new(Year::new(2024).unwrap(), day!(1));
+            let solution = Solution::new(day, |_| panic!("could not open input file"));
+
+            let output = run_solution(&solution, None, &Timeouts::default());
+            assert!(output.records.is_empty());
+            assert!(!output.timed_out);
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::protocol::{
+        read_records, PartRecord, PartStatus, PARSE_PART, RESULT_PATH_ENV,
+    };
+    use crate::template::runner::{BenchConfig, Statistic, Timeouts};
+    use crate::template::{get_bin_path, Day, YearDay};
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        env, fs,
+        io::{self, BufRead, BufReader},
+        path::{Path, PathBuf},
+        process::{self, Child, Command, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
+    /// Output captured from a solution.
+    #[derive(Debug, Default)]
+    pub struct ChildOutput {
+        /// Lines the bin printed to stdout. Empty for solutions run in-process.
+        pub stdout: Vec<String>,
+        /// Structured results reported by the bin. Empty for bins that predate the result protocol.
+        pub records: Vec<PartRecord>,
+        /// Whether the bin was stopped because it exceeded the time limit of the day.
+        pub timed_out: bool,
+    }
+
+    /// Time that a bin gets on top of the day's time limit before it is stopped.
+    /// Bins stop parts that exceed the limit themselves, so this only applies to bins that hang otherwise.
+    const KILL_GRACE: Duration = Duration::from_secs(1);
+
+    fn get_result_path(day: YearDay) -> PathBuf {
+        env::temp_dir().join(format!("aoc-results-{}-{day}.jsonl", process::id()))
+    }
+
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: YearDay,
+        bench: Option<&BenchConfig>,
+        timeouts: &Timeouts,
+        allocs: bool,
+        is_release: bool,
+    ) -> Result<ChildOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_bin_path(day)).exists() {
+            return Ok(ChildOutput::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let result_path = get_result_path(day);
+        // a stale file would mix in records from a previous run.
+        let _ = fs::remove_file(&result_path);
+
+        let bin_name = day.to_string();
+        let mut cargo_args = vec!["--quiet", "--bin", &bin_name];
 
         if is_release {
-            args.push("--release");
+            cargo_args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
+        if allocs {
+            cargo_args.extend(["--features", "alloc-stats"]);
+        }
+
+        // the time limit of the day should not include the time it takes to compile the bin.
+        if timeouts.day.is_some() {
+            let status = Command::new("cargo")
+                .arg("build")
+                .args(&cargo_args)
+                .status()?;
+
+            if !status.success() {
+                return Ok(ChildOutput::default());
+            }
+        }
+
+        let mut bin_args = vec![];
+
+        if let Some(bench) = bench {
+            // mirror `--time` flag and bench config to child invocations.
+            bin_args.push("--time".to_string());
+            bin_args.extend(bench.to_args());
+        }
+
+        bin_args.extend(timeouts.to_args());
+
+        let mut args = vec!["run"];
+        args.extend(cargo_args);
+
+        if !bin_args.is_empty() {
             args.push("--");
-            args.push("--time");
+            args.extend(bin_args.iter().map(String::as_str));
         }
 
         // spawn child command with piped stdout/stderr.
@@ -101,6 +297,7 @@ pub mod child_commands {
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .env(RESULT_PATH_ENV, &result_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,24 +305,137 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
+        let stderr_thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let stdout_thread = thread::spawn(move || {
+            stdout
+                .lines()
+                .map(|line| {
+                    let line = line.unwrap();
+                    println!("{line}");
+                    line
+                })
+                .collect::<Vec<String>>()
+        });
+
+        let timed_out = wait_with_limit(&mut cmd, timeouts.day.map(|day| day + KILL_GRACE))?;
+
+        let output = stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
+
+        let records = if result_path.exists() {
+            let records = read_records(&result_path).unwrap_or_else(|e| {
+                eprintln!("Could not read result records: {e}");
+                vec![]
+            });
+            let _ = fs::remove_file(&result_path);
+            records
+        } else {
+            vec![]
+        };
+
+        Ok(ChildOutput {
+            stdout: output,
+            records,
+            timed_out,
+        })
+    }
+
+    /// Wait for `child` to exit, killing it if it runs longer than `limit`.
+    /// Returns whether the child was killed.
+    fn wait_with_limit(child: &mut Child, limit: Option<Duration>) -> io::Result<bool> {
+        let Some(limit) = limit else {
+            child.wait()?;
+            return Ok(false);
+        };
+
+        let start = Instant::now();
+
+        loop {
+            if child.try_wait()?.is_some() {
+                return Ok(false);
+            }
+
+            if start.elapsed() >= limit {
+                child.kill()?;
+                child.wait()?;
+                return Ok(true);
+            }
+
+            thread::sleep(Duration::from_millis(10));
         }
+    }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    /// Build a [`super::Timing`] from the output of a solution bin.
+    /// If the bin was stopped, the parts it did not report on are marked as timed out.
+    pub fn timing_from_output(output: &ChildOutput, day: Day) -> super::Timing {
+        let mut timing = timing_from_records(&output.records, day);
 
-        Ok(output)
+        if output.timed_out {
+            for part in [1, 2] {
+                if !output.records.iter().any(|r| r.part == part) {
+                    timing.timed_out.push(part);
+                }
+            }
+        }
+
+        timing
+    }
+
+    /// Build a [`super::Timing`] from the structured results reported by a solution bin.
+    pub fn timing_from_records(records: &[PartRecord], day: Day) -> super::Timing {
+        let mut timings = super::Timing {
+            day,
+            part_1: None,
+            part_2: None,
+            parse: None,
+            failed: vec![],
+            timed_out: vec![],
+            allocs: vec![],
+            nanos: vec![],
+            total_nanos: 0_f64,
+            statistic: records.first().map_or(Statistic::Mean, |r| r.statistic),
+        };
+
+        let parts_with_status = |status: PartStatus| {
+            records
+                .iter()
+                .filter(|r| r.day == day && r.status == status)
+                .map(|r| r.part)
+                .collect()
+        };
+
+        timings.failed = parts_with_status(PartStatus::Failed);
+        timings.timed_out = parts_with_status(PartStatus::TimedOut);
+
+        records
+            .iter()
+            .filter(|r| r.day == day && r.status == PartStatus::Solved)
+            .for_each(|r| {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let timing_str = format!("{:.1?}", Duration::from_nanos(r.nanos as u64));
+
+                match r.part {
+                    PARSE_PART => timings.parse = Some(timing_str),
+                    1 => timings.part_1 = Some(timing_str),
+                    2 => timings.part_2 = Some(timing_str),
+                    _ => return,
+                }
+
+                if let Some(allocs) = r.allocs {
+                    timings.allocs.push((r.part, allocs));
+                }
+
+                timings.nanos.push((r.part, r.nanos));
+
+                timings.total_nanos += r.nanos;
+            });
+
+        timings
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -133,7 +443,19 @@ pub mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            parse: None,
+            failed: output
+                .iter()
+                .filter_map(|l| parse_status_line(l, "✖"))
+                .collect(),
+            timed_out: output
+                .iter()
+                .filter_map(|l| parse_status_line(l, "⏱"))
+                .collect(),
+            allocs: vec![],
+            nanos: vec![],
             total_nanos: 0_f64,
+            statistic: Statistic::Mean,
         };
 
         output
@@ -154,8 +476,10 @@ pub mod child_commands {
             .for_each(|(part, timing_str, nanos)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.nanos.push((1, nanos));
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.nanos.push((2, nanos));
                 }
 
                 timings.total_nanos += nanos;
@@ -164,6 +488,23 @@ pub mod child_commands {
         timings
     }
 
+    /// Returns the part of a line like `Part 1: ✖ <error>` or `Part 1: ⏱ timed out`, if it starts with `symbol`.
+    /// Parts without an implementation print `✖` without an error.
+    fn parse_status_line(line: &str, symbol: &str) -> Option<u8> {
+        // intermediate results are overwritten with a carriage return.
+        let line = line.rsplit('\r').next()?;
+        let (part, message) = line.split_once(&format!(": {symbol}"))?;
+
+        if message.trim().is_empty() {
+            return None;
+        }
+
+        match part {
+            "Parse" => Some(PARSE_PART),
+            part => part.strip_prefix("Part ")?.parse().ok(),
+        }
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -174,7 +515,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -205,9 +546,99 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, timing_from_output, timing_from_records, ChildOutput};
 
         use crate::day;
+        use crate::template::alloc::AllocStats;
+        use crate::template::protocol::{PartRecord, PartStatus};
+        use crate::template::runner::Statistic;
+
+        fn record(part: u8, answer: Option<&str>, nanos: f64) -> PartRecord {
+            PartRecord {
+                day: day!(1),
+                part,
+                answer: answer.map(Into::into),
+                nanos,
+                samples: 100,
+                statistic: Statistic::Median,
+                stats: None,
+                status: if answer.is_some() {
+                    PartStatus::Solved
+                } else {
+                    PartStatus::Unsolved
+                },
+                allocs: None,
+            }
+        }
+
+        #[test]
+        fn builds_timing_from_records() {
+            let res = timing_from_records(
+                &[
+                    record(1, Some("(1ms @ 5 samples)"), 74.0),
+                    record(2, Some("10"), 74_130_000.0),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.nanos, [(1, 74.0), (2, 74_130_000.0)]);
+            assert_eq!(res.statistic, Statistic::Median);
+            assert_eq!(res.parse.is_none(), true);
+        }
+
+        #[test]
+        fn builds_timing_from_parse_records() {
+            let res = timing_from_records(
+                &[record(0, Some(""), 1000.0), record(1, Some("1"), 5.0)],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 1005_f64);
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.part_1.unwrap(), "5.0ns");
+        }
+
+        #[test]
+        fn builds_timing_from_records_with_allocs() {
+            let allocs = AllocStats {
+                allocations: 2,
+                bytes: 128,
+                peak_bytes: 64,
+            };
+            let res = timing_from_records(
+                &[
+                    PartRecord {
+                        allocs: Some(allocs),
+                        ..record(2, Some("1"), 5.0)
+                    },
+                    record(1, Some("1"), 5.0),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.allocs, vec![(2, allocs)]);
+        }
+
+        #[test]
+        fn builds_timing_from_unsolved_records() {
+            let res =
+                timing_from_records(&[record(1, Some("1"), 5.0), record(2, None, 0.0)], day!(1));
+            assert_approx_eq!(res.total_nanos, 5_f64);
+            assert_eq!(res.part_1.unwrap(), "5.0ns");
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn builds_timing_from_failed_records() {
+            let failed = PartRecord {
+                status: PartStatus::Failed,
+                ..record(1, None, 100.0)
+            };
+            let res = timing_from_records(&[failed, record(2, Some("1"), 5.0)], day!(1));
+            assert_approx_eq!(res.total_nanos, 5_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.failed, vec![1]);
+        }
 
         #[test]
         fn parses_execution_times() {
@@ -252,6 +683,50 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.failed.is_empty(), true);
+        }
+
+        #[test]
+        fn parses_failed_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: ✖ panicked at src/bin/2024_01.rs:5:9: oops\rPart 1: ✖ panicked at src/bin/2024_01.rs:5:9: oops             ".into(),
+                    "Part 2: 10 (100ms @ 1 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.failed, vec![1]);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.unwrap(), "100ms");
+
+            let res = parse_exec_time(&["Parse: ✖ line 2: invalid".into()], day!(1));
+            assert_eq!(res.failed, vec![0]);
+        }
+
+        #[test]
+        fn parses_timed_out_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 10 (100ms @ 1 samples)".into(),
+                    "Part 2: ⏱ timed out (10.0s)             ".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.timed_out, vec![2]);
+            assert_eq!(res.failed.is_empty(), true);
+        }
+
+        #[test]
+        fn marks_unreported_parts_of_stopped_bins() {
+            let output = ChildOutput {
+                stdout: vec![],
+                records: vec![record(1, Some("1"), 5.0)],
+                timed_out: true,
+            };
+            let res = timing_from_output(&output, day!(1));
+            assert_eq!(res.part_1.unwrap(), "5.0ns");
+            assert_eq!(res.timed_out, vec![2]);
         }
     }
 }
@@ -1,104 +1,833 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::{mpsc, Once};
+use std::thread;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::alloc::{self, AllocStats};
+use crate::template::answers::Answers;
+use crate::template::protocol::{PartRecord, PartStatus, PARSE_PART};
+use crate::template::submissions::{Submission, Submissions, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, Day, YearDay, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Options that control how the parts of a solution are run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench each part instead of running it once.
+    pub time: bool,
+    /// The part whose result should be submitted, if any.
+    pub submit: Option<u8>,
+    /// The statistic that is reported as the duration of a benched part.
+    pub statistic: Statistic,
+    /// How long and how often to bench each part.
+    pub bench: BenchConfig,
+    /// Wall-clock limits for the parts of the solution.
+    pub timeouts: Timeouts,
+    /// When the day's time runs out, derived from `timeouts.day` when the solution starts.
+    pub deadline: Option<Instant>,
+}
+
+impl RunOptions {
+    /// Read the options from the arguments passed to a solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let submit = args.iter().position(|x| x == "--submit").map(|i| {
+            match args.get(i + 1).map(|x| x.parse::<u8>()) {
+                Some(Ok(part)) => part,
+                _ => {
+                    eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                    process::exit(1);
+                }
+            }
+        });
+
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+                .map(String::as_str)
+        };
+
+        let bench = BenchConfig::from_env().and_then(|config| {
+            config.with_overrides(
+                value_of("--bench-time"),
+                value_of("--min-samples"),
+                value_of("--max-samples"),
+            )
+        });
+
+        let bench = bench.unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+
+        let timeouts = Timeouts::from_env()
+            .and_then(|timeouts| {
+                timeouts.with_overrides(value_of("--timeout"), value_of("--day-timeout"))
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            });
+
+        Self {
+            time: args.iter().any(|x| x == "--time"),
+            submit,
+            statistic: Statistic::from_env(),
+            bench,
+            timeouts,
+            deadline: timeouts.deadline(),
+        }
+    }
+}
+
+pub const TIMEOUT_ENV: &str = "AOC_TIMEOUT";
+pub const DAY_TIMEOUT_ENV: &str = "AOC_DAY_TIMEOUT";
+
+/// Wall-clock limits for running a solution. Unset limits do not apply.
+/// A part that exceeds its limit is abandoned and reported as timed out, and the next part runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// The limit for each part, including the parse step.
+    pub part: Option<Duration>,
+    /// The limit for all parts of a day together. Parts that would start after it ran out are not run.
+    pub day: Option<Duration>,
+}
+
+impl Timeouts {
+    /// Read the limits from `AOC_TIMEOUT` and `AOC_DAY_TIMEOUT`.
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| env::var(name).ok();
+        Self::default().with_overrides(var(TIMEOUT_ENV).as_deref(), var(DAY_TIMEOUT_ENV).as_deref())
+    }
+
+    /// Returns a copy of the limits with the given values replaced.
+    pub fn with_overrides(self, part: Option<&str>, day: Option<&str>) -> Result<Self, String> {
+        let parse_timeout = |s: &str| {
+            parse_bench_time(s).map(Some).map_err(|_| {
+                format!("invalid timeout `{s}`, expecting a duration like `10s` or `500ms`.")
+            })
+        };
+
+        Ok(Self {
+            part: part.map_or(Ok(self.part), parse_timeout)?,
+            day: day.map_or(Ok(self.day), parse_timeout)?,
+        })
+    }
+
+    /// Whether a part or a day is limited at all.
+    pub fn is_limited(&self) -> bool {
+        self.part.is_some() || self.day.is_some()
+    }
+
+    /// Returns when a day that starts now runs out of time.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.day.map(|day| Instant::now() + day)
+    }
+
+    /// Arguments that pass the limits on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(part) = self.part {
+            args.extend(["--timeout".into(), format!("{}ns", part.as_nanos())]);
+        }
+
+        if let Some(day) = self.day {
+            args.extend(["--day-timeout".into(), format!("{}ns", day.as_nanos())]);
+        }
+
+        args
+    }
+}
+
+pub const BENCH_TIME_ENV: &str = "AOC_BENCH_TIME";
+pub const BENCH_MIN_SAMPLES_ENV: &str = "AOC_BENCH_MIN_SAMPLES";
+pub const BENCH_MAX_SAMPLES_ENV: &str = "AOC_BENCH_MAX_SAMPLES";
+
+/// Controls how many samples are collected when benching a part.
+/// The runner collects as many samples as fit into `time`, bounded by `min_samples` and `max_samples`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub time: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            time: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Read the config from `AOC_BENCH_TIME`, `AOC_BENCH_MIN_SAMPLES` and `AOC_BENCH_MAX_SAMPLES`.
+    /// Unset variables fall back to the defaults.
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| env::var(name).ok();
+        Self::default().with_overrides(
+            var(BENCH_TIME_ENV).as_deref(),
+            var(BENCH_MIN_SAMPLES_ENV).as_deref(),
+            var(BENCH_MAX_SAMPLES_ENV).as_deref(),
+        )
+    }
+
+    /// Returns a copy of the config with the given values replaced.
+    pub fn with_overrides(
+        self,
+        time: Option<&str>,
+        min_samples: Option<&str>,
+        max_samples: Option<&str>,
+    ) -> Result<Self, String> {
+        let parse_samples = |s: &str| {
+            s.parse::<u128>()
+                .map_err(|_| format!("invalid sample count `{s}`, expecting a positive integer."))
+        };
+
+        let config = Self {
+            time: time.map_or(Ok(self.time), parse_bench_time)?,
+            min_samples: min_samples.map_or(Ok(self.min_samples), parse_samples)?,
+            max_samples: max_samples.map_or(Ok(self.max_samples), parse_samples)?,
+        };
+
+        if config.min_samples == 0 || config.min_samples > config.max_samples {
+            return Err(format!(
+                "invalid sample bounds {}..{}, expecting 0 < min samples <= max samples.",
+                config.min_samples, config.max_samples
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// Arguments that pass this config on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--bench-time".into(),
+            format!("{}ns", self.time.as_nanos()),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ]
+    }
+}
+
+/// Parse a bench duration like `100ms`, `1.5s` or `500us`. Plain numbers are read as seconds.
+pub fn parse_bench_time(s: &str) -> Result<Duration, String> {
+    let err = || format!("invalid bench time `{s}`, expecting a duration like `100ms` or `10s`.");
+
+    let (value, unit_nanos) = [
+        ("ns", 1_f64),
+        ("µs", 1e3),
+        ("us", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+    ]
+    .iter()
+    .find_map(|(suffix, nanos)| s.strip_suffix(suffix).map(|v| (v, *nanos)))
+    .unwrap_or((s, 1e9));
+
+    let value: f64 = value.trim().parse().map_err(|_| err())?;
+
+    if !value.is_finite() || value <= 0.0 {
+        return Err(err());
+    }
+
+    Ok(Duration::from_secs_f64(value * unit_nanos / 1e9))
+}
+
+pub const BENCH_STATISTIC_ENV: &str = "AOC_BENCH_STATISTIC";
+
+/// A statistic over the samples of a benchmark run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Statistic {
+    #[default]
+    Mean,
+    Median,
+    Min,
+    P95,
+}
+
+impl Statistic {
+    /// Read the statistic from `AOC_BENCH_STATISTIC`, falling back to the mean.
+    pub fn from_env() -> Self {
+        match env::var(BENCH_STATISTIC_ENV) {
+            Ok(x) => x.parse().unwrap_or_else(|e| {
+                eprintln!("{e}");
+                Statistic::default()
+            }),
+            Err(_) => Statistic::default(),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Statistic::Mean => "mean",
+            Statistic::Median => "median",
+            Statistic::Min => "min",
+            Statistic::P95 => "p95",
+        }
+    }
+}
+
+impl Display for Statistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Statistic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mean" => Ok(Statistic::Mean),
+            "median" => Ok(Statistic::Median),
+            "min" => Ok(Statistic::Min),
+            "p95" => Ok(Statistic::P95),
+            x => Err(format!(
+                "unknown statistic `{x}`, expecting one of: mean, median, min, p95."
+            )),
+        }
+    }
+}
+
+/// Summary of the samples collected while benching a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    /// Number of samples left after outlier rejection.
+    pub samples: u128,
+}
+
+impl BenchStats {
+    /// Compute the stats of a set of samples, ignoring outliers outside of Tukey's fences (1.5 IQR).
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn from_samples(timers: &[Duration]) -> Self {
+        let mut sorted: Vec<u128> = timers.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let fence = (q3 - q1) * 3 / 2;
+        let lower = q1.saturating_sub(fence);
+        let upper = q3 + fence;
+
+        let retained: Vec<u128> = sorted
+            .into_iter()
+            .filter(|x| *x >= lower && *x <= upper)
+            .collect();
+
+        let count = retained.len() as f64;
+        let mean = retained.iter().sum::<u128>() as f64 / count;
+        let variance = retained
+            .iter()
+            .map(|x| (*x as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Self {
+            min: Duration::from_nanos(retained[0] as u64),
+            median: Duration::from_nanos(percentile(&retained, 0.5) as u64),
+            p95: Duration::from_nanos(percentile(&retained, 0.95) as u64),
+            mean: Duration::from_nanos(mean as u64),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+            samples: retained.len() as u128,
+        }
+    }
+
+    /// Returns the value of the given statistic.
+    pub fn get(&self, statistic: Statistic) -> Duration {
+        match statistic {
+            Statistic::Mean => self.mean,
+            Statistic::Median => self.median,
+            Statistic::Min => self.min,
+            Statistic::P95 => self.p95,
+        }
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[u128], p: f64) -> u128 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// What a solution part returned, see [`PartResult`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PartOutcome<T> {
+    /// The part returned an answer.
+    Solved(T),
+    /// The part returned `None`, e.g. because it is not implemented yet.
+    Unsolved,
+    /// The part returned an error or panicked, which is kept as its message.
+    Failed(String),
+    /// The part exceeded its time limit after running for the given time, and was abandoned.
+    TimedOut(Duration),
+}
+
+/// Return types that solution parts can use: `Option<T>` and `Result<T, E>`.
+/// `None` marks a part as not solved yet, while an error is printed in place of the answer.
+pub trait PartResult {
+    type Answer: Display;
+
+    fn into_outcome(self) -> PartOutcome<Self::Answer>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_outcome(self) -> PartOutcome<T> {
+        match self {
+            Some(answer) => PartOutcome::Solved(answer),
+            None => PartOutcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_outcome(self) -> PartOutcome<T> {
+        match self {
+            Ok(answer) => PartOutcome::Solved(answer),
+            Err(e) => PartOutcome::Failed(e.to_string()),
+        }
+    }
+}
+
+pub fn run_part<I, R, F>(func: F, input: I, day: YearDay, part: u8, options: &RunOptions)
+where
+    I: Clone + Send + 'static,
+    R: PartResult,
+    R::Answer: Send + 'static,
+    F: Fn(I) -> R + Clone + Send + 'static,
+{
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let TimedRun {
+        result,
+        duration,
+        stats,
+        allocs,
+    } = run_timed(
+        move |input| func(input).into_outcome(),
+        input,
+        part,
+        options,
+        |result| {
+            print_result(result, &part_str, "");
+        },
+    );
+
+    let result = result.unwrap_or_else(|e| match e {
+        Interruption::Panicked(message) => PartOutcome::Failed(message),
+        Interruption::TimedOut => PartOutcome::TimedOut(duration),
+    });
+    let duration = stats.map_or(duration, |stats| stats.get(options.statistic));
+
+    print_result(
+        &result,
+        &part_str,
+        &format!(
+            "{}{}",
+            format_duration(&duration, stats.as_ref(), options.statistic),
+            format_allocs(allocs.as_ref())
+        ),
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (answer, status) = match &result {
+        PartOutcome::Solved(answer) => (Some(answer.to_string()), PartStatus::Solved),
+        PartOutcome::Unsolved => (None, PartStatus::Unsolved),
+        PartOutcome::Failed(_) => (None, PartStatus::Failed),
+        PartOutcome::TimedOut(_) => (None, PartStatus::TimedOut),
+    };
+
+    emit_record(PartRecord {
+        answer,
+        status,
+        allocs,
+        ..timed_record(day.day, part, &duration, stats, options.statistic)
+    });
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let PartOutcome::Solved(result) = result {
+        if options.submit == Some(part) {
+            submit_result(&result, day, part);
+        }
     }
 }
 
+/// Run the parse step of a solution and return its output, which is shared by all parts.
+/// The parse step is timed (and benched) separately from the parts.
+/// If it returns an error or times out, this is printed and `None` is returned, so that the parts are skipped.
+pub fn run_parse<P, E, F>(
+    func: F,
+    input: &'static str,
+    day: YearDay,
+    options: &RunOptions,
+) -> Option<P>
+where
+    P: Send + 'static,
+    E: Display,
+    F: Fn(&'static str) -> Result<P, E> + Clone + Send + 'static,
+{
+    let TimedRun {
+        result: parsed,
+        duration,
+        stats,
+        allocs,
+    } = run_timed(
+        move |input| func(input).map_err(|e| e.to_string()),
+        input,
+        PARSE_PART,
+        options,
+        |_| {
+            print!("Parse:");
+        },
+    );
+
+    let base_time = duration;
+    let duration = stats.map_or(duration, |stats| stats.get(options.statistic));
+
+    print!("\r");
+
+    let (parsed, status) = match parsed {
+        Ok(Ok(parsed)) => {
+            println!(
+                "Parse:{}{}",
+                format_duration(&duration, stats.as_ref(), options.statistic),
+                format_allocs(allocs.as_ref())
+            );
+            (Some(parsed), PartStatus::Solved)
+        }
+        Ok(Err(message)) | Err(Interruption::Panicked(message)) => {
+            println!("Parse: ✖ {message}             ");
+            (None, PartStatus::Failed)
+        }
+        Err(Interruption::TimedOut) => {
+            println!("Parse: ⏱ timed out ({base_time:.1?})             ");
+            (None, PartStatus::TimedOut)
+        }
+    };
+
+    emit_record(PartRecord {
+        status,
+        allocs,
+        ..timed_record(day.day, PARSE_PART, &duration, stats, options.statistic)
+    });
+
+    parsed
+}
+
+/// Why the first run of a part did not return.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Interruption {
+    /// The part panicked, with the panic's message and location.
+    Panicked(String),
+    /// The part did not return within its time limit and was abandoned.
+    TimedOut,
+}
+
+/// The outcome of [`run_timed`].
+struct TimedRun<T> {
+    result: Result<T, Interruption>,
+    /// Duration of the first run.
+    duration: Duration,
+    /// Present if the part was benched.
+    stats: Option<BenchStats>,
+    /// Allocations of the first run, present if the counting allocator is installed.
+    allocs: Option<AllocStats>,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///  2. with `--time`, the function is benched (approx. the configured bench time or the minimum number of samples, whatever takes longer.)
+///
+/// If the first run panics or exceeds the time limit of the part, the part is not benched.
+/// With a time limit, the first run happens on a separate thread, which is abandoned when the limit is exceeded.
+/// Allocations are only counted (and profiled with DHAT) for the first run.
+fn run_timed<I, T, F>(
+    func: F,
     input: I,
+    // only used to name the DHAT report of the part.
+    #[cfg_attr(not(feature = "dhat-heap"), allow(unused_variables))] part: u8,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> TimedRun<T>
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Clone + Send + 'static,
+{
     let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+    let result = match time_limit(options) {
+        None => catch_panic(|| {
+            let input = input.clone();
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+            #[cfg(feature = "dhat-heap")]
+            let _profiler = dhat_profiler(part);
 
-        func(input)
+            alloc::measure(|| func(input))
+        })
+        .map_err(Interruption::Panicked),
+        Some(limit) => {
+            let func = func.clone();
+            run_with_time_limit(
+                move |input| {
+                    #[cfg(feature = "dhat-heap")]
+                    let _profiler = dhat_profiler(part);
+
+                    alloc::measure(|| func(input))
+                },
+                input.clone(),
+                limit,
+            )
+        }
     };
     let base_time = timer.elapsed();
 
+    let (result, allocs) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            return TimedRun {
+                result: Err(e),
+                duration: base_time,
+                stats: None,
+                allocs: None,
+            }
+        }
+    };
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if options.time {
+        let part_deadline = options.timeouts.part.map(|limit| timer + limit);
+        let deadline = match (part_deadline, options.deadline) {
+            (Some(part), Some(day)) => Some(cmp::min(part, day)),
+            (part, day) => part.or(day),
+        };
+
+        match bench(func, input, &base_time, &options.bench, deadline) {
+            Ok(stats) => Some(stats),
+            // a part that panics on a later run fails just like one that panics on the first run.
+            Err(e) => {
+                return TimedRun {
+                    result: Err(Interruption::Panicked(e)),
+                    duration: base_time,
+                    stats: None,
+                    allocs,
+                }
+            }
+        }
     } else {
-        (base_time, 1)
+        None
     };
 
-    (result, run.0, run.1)
+    TimedRun {
+        result: Ok(result),
+        duration: base_time,
+        stats,
+        allocs,
+    }
+}
+
+/// The time that the next part may take, which is bounded by both the part's and the day's limit.
+fn time_limit(options: &RunOptions) -> Option<Duration> {
+    let remaining = options
+        .deadline
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+    match (options.timeouts.part, remaining) {
+        (Some(part), Some(remaining)) => Some(cmp::min(part, remaining)),
+        (part, remaining) => part.or(remaining),
+    }
+}
+
+/// Run `func` on a separate thread and wait for at most `limit` for it to return.
+/// A thread that exceeds the limit keeps running in the background until the process exits.
+fn run_with_time_limit<I, T, F>(func: F, input: I, limit: Duration) -> Result<T, Interruption>
+where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: FnOnce(I) -> T + Send + 'static,
+{
+    if limit.is_zero() {
+        return Err(Interruption::TimedOut);
+    }
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::Builder::new()
+        .name("solution".into())
+        // solutions are written for the main thread, which usually has a larger stack.
+        .stack_size(MAIN_STACK_SIZE)
+        .spawn(move || {
+            // the receiver is gone if the part timed out.
+            let _ = sender.send(catch_panic(|| func(input)));
+        })
+        .expect("failed to spawn a thread for the solution");
+
+    match receiver.recv_timeout(limit) {
+        Ok(result) => result.map_err(Interruption::Panicked),
+        Err(_) => Err(Interruption::TimedOut),
+    }
+}
+
+/// Start profiling the heap with DHAT. The report is written to a separate file for every part.
+#[cfg(feature = "dhat-heap")]
+fn dhat_profiler(part: u8) -> dhat::Profiler {
+    dhat::Profiler::builder()
+        .file_name(crate::template::heap_report::report_path(part))
+        .build()
+}
+
+const MAIN_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+thread_local! {
+    /// Whether a panic on this thread is caught by [`catch_panic`].
+    static CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Run `f`, turning a panic into an error message that holds the panic's message and location.
+/// Panics that are caught this way are not reported by the default panic hook.
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static INSTALL_HOOK: Once = Once::new();
+
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING_PANIC.get() {
+                PANIC_LOCATION.set(info.location().map(ToString::to_string));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    CATCHING_PANIC.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_PANIC.set(false);
+
+    result.map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+
+        match PANIC_LOCATION.take() {
+            Some(location) => format!("panicked at {location}: {message}"),
+            None => format!("panicked: {message}"),
+        }
+    })
+}
+
+/// Bench a solution part. Runs a short warmup phase before collecting samples, so that
+/// first-iteration effects like cache misses do not skew the result.
+/// Sampling stops early once `deadline` (the earlier of the part's and the day's) has passed, and benching stops as soon as an iteration panics.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    deadline: Option<Instant>,
+) -> Result<BenchStats, String> {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = (config.time.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(config.min_samples, config.max_samples);
+
+    let warmup_iterations = cmp::max(bench_iterations / 10, 1);
+    let is_past_deadline = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+
+    for _ in 0..warmup_iterations {
+        if is_past_deadline() {
+            break;
+        }
+        catch_panic(|| black_box(func(black_box(input.clone()))))?;
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
+        if is_past_deadline() {
+            break;
+        }
+
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
-        let timer = Instant::now();
-        black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+        let elapsed = catch_panic(|| {
+            let timer = Instant::now();
+            black_box(func(black_box(cloned)));
+            timer.elapsed()
+        })?;
+        timers.push(elapsed);
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // the first run is the only sample if the deadline passed before sampling.
+    if timers.is_empty() {
+        timers.push(*base_time);
+    }
+
+    Ok(BenchStats::from_samples(&timers))
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(
+    duration: &Duration,
+    stats: Option<&BenchStats>,
+    statistic: Statistic,
+) -> String {
+    match stats {
+        None => format!(" ({duration:.1?})"),
+        Some(stats) => format!(
+            " ({duration:.1?} @ {} samples) {ANSI_ITALIC}[{statistic} | min {:.1?} | median {:.1?} | p95 {:.1?} | σ {:.1?}]{ANSI_RESET}",
+            stats.samples, stats.min, stats.median, stats.p95, stats.stddev
+        ),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_allocs(allocs: Option<&AllocStats>) -> String {
+    match allocs {
+        None => String::new(),
+        Some(allocs) => format!(" {ANSI_ITALIC}[{}]{ANSI_RESET}", allocs.summary()),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &PartOutcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        PartOutcome::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -118,7 +847,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        PartOutcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,44 +855,314 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        PartOutcome::Failed(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖ {e}");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {e}             ");
+            }
+        }
+        PartOutcome::TimedOut(duration) => {
+            if is_intermediate_result {
+                print!("{part}: ⏱ timed out");
+            } else {
+                print!("\r");
+                println!("{part}: ⏱ timed out ({duration:.1?})             ");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+/// Create a record holding the timing of a part. The answer and status are left empty.
+#[allow(clippy::cast_precision_loss)]
+fn timed_record(
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    duration: &Duration,
+    stats: Option<BenchStats>,
+    statistic: Statistic,
+) -> PartRecord {
+    PartRecord {
+        day,
+        part,
+        answer: None,
+        nanos: duration.as_nanos() as f64,
+        samples: stats.map_or(1, |stats| stats.samples),
+        statistic,
+        stats,
+        status: PartStatus::Unsolved,
+        allocs: None,
+    }
+}
+
+/// Report the result of a part to the runner if it requested structured output.
+fn emit_record(record: PartRecord) {
+    if let Err(e) = record.emit() {
+        eprintln!("Failed to write result record: {e}");
+    }
+}
+
+/// Remember a correct answer, so that `cargo verify` can check it later.
+fn store_answer<T: Display>(result: &T, day: YearDay, part: u8) {
+    let mut answers = Answers::read_from_file(day.year);
+    answers.set(day.day, part, &result.to_string());
+
+    match answers.store_file(day.year) {
+        Ok(()) => println!("Stored answer for verification."),
+        Err(e) => eprintln!("Failed to store answer: {e}"),
+    }
+}
+
+/// Submit one part of the solution, unless previous submissions show that the answer is wrong.
+/// The server's verdict is logged, and correct answers are stored for `cargo verify`.
+fn submit_result<T: Display>(result: &T, day: YearDay, part: u8) {
+    let answer = result.to_string();
+    let mut submissions = Submissions::read_from_file(day.year);
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    if let Err(refusal) = submissions.check(day.day, part, &answer) {
+        eprintln!("Refusing to submit `{answer}`: {refusal}");
+        return;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let response = aoc_client::Backend::from_env().and_then(|backend| {
+        println!("Submitting result...");
+        backend.submit(day, part, &answer)
+    });
+
+    let verdict = match response {
+        Ok(response) => Verdict::from_response(&response),
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            return;
+        }
+    };
+
+    submissions.add(Submission::new(day.day, part, &answer, verdict));
+
+    if let Err(e) = submissions.store_file(day.year) {
+        eprintln!("Failed to store submission: {e}");
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    if verdict == Verdict::Correct {
+        store_answer(&answer, day, part);
+    }
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        bench, catch_panic, parse_bench_time, run_with_time_limit, time_limit, BenchConfig,
+        BenchStats, Interruption, PartOutcome, PartResult, RunOptions, Statistic, Timeouts,
     };
+    use std::cell::Cell;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_bench_stats() {
+        let stats = BenchStats::from_samples(&nanos(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.p95, Duration::from_nanos(5));
+        assert_eq!(stats.mean, Duration::from_nanos(3));
+        assert_eq!(stats.stddev, Duration::from_nanos(1));
+        assert_eq!(stats.samples, 5);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 1000]));
+        assert_eq!(stats.samples, 6);
+        assert_eq!(stats.get(Statistic::Min), Duration::from_nanos(10));
+        assert_eq!(stats.get(Statistic::P95), Duration::from_nanos(12));
+        assert_eq!(stats.get(Statistic::Mean), Duration::from_nanos(10));
+    }
 
-    if part_submit != part {
-        return None;
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.stddev, Duration::ZERO);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn converts_part_results() {
+        assert_eq!(Some(42).into_outcome(), PartOutcome::Solved(42));
+        assert_eq!(None::<u32>.into_outcome(), PartOutcome::Unsolved);
+        assert_eq!(Ok::<_, String>(42).into_outcome(), PartOutcome::Solved(42));
+        assert_eq!(
+            Err::<u32, _>("line 3: invalid number").into_outcome(),
+            PartOutcome::Failed("line 3: invalid number".into())
+        );
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let err = catch_panic(|| -> u32 { panic!("no solution for {}", 3) }).unwrap_err();
+        assert!(err.starts_with("panicked at src/template/runner.rs:"));
+        assert!(err.ends_with(": no solution for 3"));
+
+        let err = catch_panic(|| std::panic::panic_any(42)).unwrap_err();
+        assert!(err.ends_with(": Box<dyn Any>"));
+    }
+
+    #[test]
+    fn stops_benching_on_panics() {
+        let config = BenchConfig {
+            time: Duration::from_millis(1),
+            min_samples: 20,
+            max_samples: 20,
+        };
+        let base_time = Duration::from_micros(1);
+
+        let runs = Cell::new(0);
+        let flaky = |x: u32| {
+            runs.set(runs.get() + 1);
+            assert!(runs.get() < 5, "failed on run {}", runs.get());
+            x
+        };
+        let err = bench(flaky, 1, &base_time, &config, None).unwrap_err();
+        assert!(err.ends_with(": failed on run 5"));
+        assert_eq!(runs.get(), 5);
+
+        // 2 warmup iterations and 20 samples.
+        runs.set(0);
+        let counted = |x: u32| {
+            runs.set(runs.get() + 1);
+            x
+        };
+        assert!(bench(counted, 1, &base_time, &config, None).is_ok());
+        assert_eq!(runs.get(), 22);
+    }
+
+    #[test]
+    fn stops_benching_at_the_deadline() {
+        let config = BenchConfig {
+            time: Duration::from_secs(1),
+            min_samples: 20,
+            max_samples: 20,
+        };
+        let base_time = Duration::from_millis(5);
+
+        let runs = Cell::new(0);
+        let counted = |x: u32| {
+            runs.set(runs.get() + 1);
+            x
+        };
+        let stats = bench(counted, 1, &base_time, &config, Some(Instant::now())).unwrap();
+        assert_eq!(runs.get(), 0);
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.min, base_time);
+    }
+
+    #[test]
+    fn abandons_parts_that_time_out() {
+        let limit = Duration::from_millis(20);
+        assert_eq!(run_with_time_limit(|x: u32| x + 1, 41, limit), Ok(42));
+
+        let slow = |_: ()| thread::sleep(Duration::from_secs(5));
+        let timer = Instant::now();
+        assert_eq!(
+            run_with_time_limit(slow, (), limit),
+            Err(Interruption::TimedOut)
+        );
+        assert!(timer.elapsed() < Duration::from_secs(1));
+
+        let panics = |_: ()| -> u32 { panic!("oops") };
+        assert!(matches!(
+            run_with_time_limit(panics, (), limit),
+            Err(Interruption::Panicked(_))
+        ));
+    }
+
+    #[test]
+    fn limits_parts_by_the_day() {
+        let timeouts = Timeouts {
+            part: Some(Duration::from_secs(10)),
+            day: Some(Duration::from_secs(60)),
+        };
+        let options = RunOptions {
+            timeouts,
+            deadline: timeouts.deadline(),
+            ..RunOptions::default()
+        };
+        assert_eq!(time_limit(&options), Some(Duration::from_secs(10)));
+
+        let options = RunOptions {
+            deadline: Some(Instant::now()),
+            ..options
+        };
+        assert_eq!(time_limit(&options), Some(Duration::ZERO));
+        assert_eq!(time_limit(&RunOptions::default()), None);
+        assert!(timeouts.is_limited());
+        assert!(!Timeouts::default().is_limited());
+    }
+
+    #[test]
+    fn roundtrips_timeout_args() {
+        let timeouts = Timeouts::default()
+            .with_overrides(Some("500ms"), Some("1m"))
+            .unwrap_err();
+        assert!(timeouts.contains("invalid timeout `1m`"));
+
+        let timeouts = Timeouts::default()
+            .with_overrides(Some("500ms"), Some("30s"))
+            .unwrap();
+        assert_eq!(timeouts.part, Some(Duration::from_millis(500)));
+        let args = timeouts.to_args();
+        let reparsed = Timeouts::default()
+            .with_overrides(Some(&args[1]), Some(&args[3]))
+            .unwrap();
+        assert_eq!(reparsed, timeouts);
+        assert!(Timeouts::default().to_args().is_empty());
+    }
+
+    #[test]
+    fn parses_statistics() {
+        assert_eq!("median".parse::<Statistic>(), Ok(Statistic::Median));
+        assert_eq!("p95".parse::<Statistic>(), Ok(Statistic::P95));
+        assert!("p99".parse::<Statistic>().is_err());
+    }
+
+    #[test]
+    fn parses_bench_times() {
+        assert_eq!(parse_bench_time("100ms"), Ok(Duration::from_millis(100)));
+        assert_eq!(parse_bench_time("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_bench_time("500us"), Ok(Duration::from_micros(500)));
+        assert_eq!(parse_bench_time("10"), Ok(Duration::from_secs(10)));
+        assert!(parse_bench_time("fast").is_err());
+        assert!(parse_bench_time("-1s").is_err());
+    }
+
+    #[test]
+    fn overrides_bench_config() {
+        let config = BenchConfig::default()
+            .with_overrides(Some("100ms"), None, Some("50"))
+            .unwrap();
+        assert_eq!(config.time, Duration::from_millis(100));
+        assert_eq!(config.min_samples, 10);
+        assert_eq!(config.max_samples, 50);
+        assert!(BenchConfig::default()
+            .with_overrides(None, Some("100"), Some("50"))
+            .is_err());
+    }
+
+    #[test]
+    fn roundtrips_bench_config_args() {
+        let config = BenchConfig::default()
+            .with_overrides(Some("2.5s"), Some("3"), Some("7"))
+            .unwrap();
+        let args = config.to_args();
+        let reparsed = BenchConfig::default()
+            .with_overrides(Some(&args[1]), Some(&args[3]), Some(&args[5]))
+            .unwrap();
+        assert_eq!(reparsed, config);
+    }
 }
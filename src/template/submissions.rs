@@ -0,0 +1,441 @@
+/// Logs every submitted answer and the server's verdict, so that answers that are known to be wrong
+/// are not sent again.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{get_data_dir, Day, Year};
+
+fn get_file_path(year: Year) -> String {
+    format!("{}/submissions.json", get_data_dir(year))
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, without a hint in which direction.
+    Wrong,
+    /// The answer was not checked because it was submitted too soon after a previous one.
+    RateLimited,
+    /// The response could not be interpreted, e.g. because the part was already solved.
+    Unknown,
+}
+
+impl Verdict {
+    /// Interpret the response to a submission.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else if response.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if response.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if response.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    /// Returns true if the server checked the answer and rejected it.
+    fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str().replace('_', " "))
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            "unknown" => Ok(Verdict::Unknown),
+            x => Err(format!("unknown verdict `{x}`.")),
+        }
+    }
+}
+
+/// Reason for not sending an answer to the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved with the contained answer.
+    AlreadySolved(String),
+    /// The same answer was submitted before and rejected.
+    KnownWrong(Verdict),
+    /// The answer is not above the contained answer, which was too low.
+    BelowBound(String),
+    /// The answer is not below the contained answer, which was too high.
+    AboveBound(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(x) => write!(f, "this part was already solved with `{x}`."),
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "this answer was submitted before and was {verdict}.")
+            }
+            Refusal::BelowBound(x) => write!(f, "`{x}` was already too low."),
+            Refusal::AboveBound(x) => write!(f, "`{x}` was already too high."),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub submitted_at: u64,
+}
+
+impl Submission {
+    pub fn new(day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        Self {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            submitted_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+/// Represents all submissions, in the order they were made.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(get_data_dir(year))?;
+        let mut file = fs::File::create(get_file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns no submissions.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(get_file_path(year))
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from);
+
+        match s {
+            Ok(submissions) => submissions,
+            Err(e) => {
+                eprintln!("{e}");
+                Submissions::default()
+            }
+        }
+    }
+
+    pub fn add(&mut self, submission: Submission) {
+        self.data.push(submission);
+    }
+
+    /// Check an answer against previous submissions for the same part.
+    /// Returns the reason if the answer should not be submitted.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let previous: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(correct) = previous.iter().find(|s| s.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved(correct.answer.clone()));
+        }
+
+        if let Some(wrong) = previous
+            .iter()
+            .find(|s| s.verdict.is_wrong() && s.answer == answer)
+        {
+            return Err(Refusal::KnownWrong(wrong.verdict));
+        }
+
+        // bounds can only be learned for numeric answers.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            previous
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, &s.answer)))
+        };
+
+        if let Some((_, lower)) = bound(Verdict::TooLow)
+            .filter(|(x, _)| value <= *x)
+            .max_by_key(|(x, _)| *x)
+        {
+            return Err(Refusal::BelowBound(lower.clone()));
+        }
+
+        if let Some((_, upper)) = bound(Verdict::TooHigh)
+            .filter(|(x, _)| value >= *x)
+            .min_by_key(|(x, _)| *x)
+        {
+            return Err(Refusal::AboveBound(upper.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        map.insert(
+            "submitted_at".into(),
+            JsonValue::Number(value.submitted_at as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        let submitted_at = json
+            .get("submitted_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected submission.submitted_at to be a number.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            submitted_at,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Refusal, Submission, Submissions, Verdict};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    fn submissions(entries: &[(u8, &str, Verdict)]) -> Submissions {
+        Submissions {
+            data: entries
+                .iter()
+                .map(|(part, answer, verdict)| Submission::new(day!(1), *part, answer, *verdict))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn interprets_responses() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Verdict::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, ...",
+                Verdict::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait.",
+                Verdict::RateLimited,
+            ),
+            (
+                "You don't seem to be solving the right level.",
+                Verdict::Unknown,
+            ),
+        ];
+
+        for (response, verdict) in cases {
+            assert_eq!(Verdict::from_response(response), verdict);
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions =
+            submissions(&[(1, "abc", Verdict::Wrong), (1, "def", Verdict::RateLimited)]);
+        assert_eq!(
+            submissions.check(day!(1), 1, "abc"),
+            Err(Refusal::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "def"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "abc"), Ok(()));
+        assert_eq!(submissions.check(day!(2), 1, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_of_bounds() {
+        let submissions = submissions(&[
+            (1, "100", Verdict::TooLow),
+            (1, "120", Verdict::TooLow),
+            (1, "200", Verdict::TooHigh),
+        ]);
+        assert_eq!(
+            submissions.check(day!(1), 1, "110"),
+            Err(Refusal::BelowBound("120".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "250"),
+            Err(Refusal::AboveBound("200".into()))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "150"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "xyz"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let submissions = submissions(&[(2, "150", Verdict::Correct)]);
+        assert_eq!(
+            submissions.check(day!(1), 2, "151"),
+            Err(Refusal::AlreadySolved("150".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = submissions(&[(1, "100", Verdict::TooLow), (2, "42", Verdict::Correct)]);
+        let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_submissions() {
+        let json = r#"{ "data": [{ "day": "01", "part": 1, "answer": "1" }] }"#.to_string();
+        Submissions::try_from(json).unwrap();
+    }
+}
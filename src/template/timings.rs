@@ -1,9 +1,14 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::alloc::AllocStats;
+use crate::template::protocol::PARSE_PART;
+use crate::template::runner::{parse_bench_time, Statistic};
+use crate::template::{get_data_dir, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+fn get_file_path(year: Year) -> String {
+    format!("{}/timings.json", get_data_dir(year))
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,7 +16,46 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Time spent in the shared parse step, for solutions that have one.
+    pub parse: Option<String>,
+    /// Parts that returned an error instead of an answer, with `0` standing for the parse step.
+    /// Their times are not recorded.
+    pub failed: Vec<u8>,
+    /// Parts that exceeded their time limit, with `0` standing for the parse step.
+    pub timed_out: Vec<u8>,
+    /// Allocations of the solved parts, with `0` standing for the parse step.
+    /// Only present for solutions that were built with the `alloc-stats` feature.
+    pub allocs: Vec<(u8, AllocStats)>,
+    /// Exact times of the solved parts in nanoseconds, with `0` standing for the parse step.
+    /// `part_1`, `part_2` and `parse` hold the same times, rounded for display.
+    pub nanos: Vec<(u8, f64)>,
     pub total_nanos: f64,
+    /// The statistic that `part_1` and `part_2` show.
+    pub statistic: Statistic,
+}
+
+impl Timing {
+    /// Returns the allocations of a part, if they were counted.
+    pub fn allocs(&self, part: u8) -> Option<&AllocStats> {
+        self.allocs.iter().find(|(p, _)| *p == part).map(|(_, a)| a)
+    }
+
+    /// Returns the time of a part in nanoseconds, if it was solved.
+    /// Timings stored before exact times were recorded fall back to the rounded display time.
+    pub fn nanos(&self, part: u8) -> Option<f64> {
+        if let Some((_, nanos)) = self.nanos.iter().find(|(p, _)| *p == part) {
+            return Some(*nanos);
+        }
+
+        let time = match part {
+            PARSE_PART => &self.parse,
+            1 => &self.part_1,
+            2 => &self.part_2,
+            _ => return None,
+        };
+
+        Some(parse_bench_time(time.as_deref()?).ok()?.as_secs_f64() * 1e9)
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -23,15 +67,16 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(get_data_dir(year))?;
+        let mut file = fs::File::create(get_file_path(year))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(get_file_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -58,7 +103,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -120,9 +165,14 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+        map.insert(
+            "statistic".into(),
+            JsonValue::String(value.statistic.as_str().into()),
+        );
 
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
+        let parse = value.parse.clone().map(JsonValue::String);
 
         map.insert(
             "part_1".into(),
@@ -140,6 +190,37 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            match parse {
+                Some(x) => x,
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert("failed".into(), parts_to_json(&value.failed));
+        map.insert("timed_out".into(), parts_to_json(&value.timed_out));
+        map.insert(
+            "allocs".into(),
+            JsonValue::Object(
+                value
+                    .allocs
+                    .iter()
+                    .map(|(part, allocs)| (part.to_string(), allocs.into()))
+                    .collect(),
+            ),
+        );
+        map.insert(
+            "nanos".into(),
+            JsonValue::Object(
+                value
+                    .nanos
+                    .iter()
+                    .map(|(part, nanos)| (part.to_string(), JsonValue::Number(*nanos)))
+                    .collect(),
+            ),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -168,25 +249,129 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // timings stored before parse steps were timed separately have no `parse` key.
+        let parse = match json.get("parse") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        // timings stored before failed parts were tracked have no `failed` or `timed_out` keys.
+        let failed = parts_from_json(json.get("failed"), "failed")?;
+        let timed_out = parts_from_json(json.get("timed_out"), "timed_out")?;
+
+        // timings stored without the `alloc-stats` feature have no `allocs` key.
+        let allocs = match json.get("allocs") {
+            Some(v) => allocs_from_json(v)?,
+            None => vec![],
+        };
+
+        // timings stored before exact times were recorded have no `nanos` key.
+        let nanos = match json.get("nanos") {
+            Some(v) => nanos_from_json(v)?,
+            None => vec![],
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // timings stored before statistics were configurable are means.
+        let statistic = match json.get("statistic") {
+            Some(v) => v
+                .get::<String>()
+                .ok_or("Expected timing.statistic to be a string.")?
+                .parse()?,
+            None => Statistic::Mean,
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse: parse.cloned(),
+            failed,
+            timed_out,
+            allocs,
+            nanos,
             total_nanos,
+            statistic,
         })
     }
 }
 
+fn parts_to_json(parts: &[u8]) -> JsonValue {
+    JsonValue::Array(
+        parts
+            .iter()
+            .map(|part| JsonValue::Number(f64::from(*part)))
+            .collect(),
+    )
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parts_from_json(value: Option<&JsonValue>, key: &str) -> Result<Vec<u8>, String> {
+    let Some(value) = value else {
+        return Ok(vec![]);
+    };
+
+    value
+        .get::<Vec<JsonValue>>()
+        .ok_or(format!("Expected timing.{key} to be an array."))?
+        .iter()
+        .map(|part| {
+            part.get::<f64>()
+                .map(|part| *part as u8)
+                .ok_or(format!("Expected timing.{key} to contain part numbers."))
+        })
+        .collect()
+}
+
+fn allocs_from_json(value: &JsonValue) -> Result<Vec<(u8, AllocStats)>, String> {
+    let mut allocs = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing.allocs to be a JSON object.")?
+        .iter()
+        .map(|(part, allocs)| {
+            let part = part.parse().map_err(|_| {
+                format!("Expected timing.allocs to be keyed by part, found `{part}`.")
+            })?;
+            Ok((part, AllocStats::try_from(allocs)?))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    allocs.sort_unstable_by_key(|(part, _)| *part);
+    Ok(allocs)
+}
+
+fn nanos_from_json(value: &JsonValue) -> Result<Vec<(u8, f64)>, String> {
+    let mut nanos = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing.nanos to be a JSON object.")?
+        .iter()
+        .map(|(part, nanos)| {
+            let part = part.parse().map_err(|_| {
+                format!("Expected timing.nanos to be keyed by part, found `{part}`.")
+            })?;
+            let nanos = nanos
+                .get::<f64>()
+                .ok_or("Expected timing.nanos to contain numbers.")?;
+            Ok((part, *nanos))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    nanos.sort_unstable_by_key(|(part, _)| *part);
+    Ok(nanos)
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, template::runner::Statistic};
 
     use super::{Timing, Timings};
 
@@ -197,26 +382,48 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 3e+10,
+                    statistic: Statistic::Mean,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 7e+10,
+                    statistic: Statistic::Mean,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 4e+10,
+                    statistic: Statistic::Mean,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::runner::Statistic,
+            template::timings::{Timing, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -228,6 +435,75 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.statistic, Statistic::Mean);
+        }
+
+        #[test]
+        fn handles_statistics() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "statistic": "median" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].statistic, Statistic::Median);
+        }
+
+        #[test]
+        fn handles_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "parse": "2ms", "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].parse, Some("2ms".to_string()));
+        }
+
+        #[test]
+        fn handles_failed_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": "1ms", "failed": [1], "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].failed, vec![1]);
+            assert_eq!(timings.data[0].part_1, None);
+            assert_eq!(timings.data[0].timed_out.is_empty(), true);
+        }
+
+        #[test]
+        fn handles_timed_out_parts() {
+            let json = r#"{ "data": [{ "day": "06", "part_1": "1ms", "part_2": null, "failed": [], "timed_out": [2], "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].timed_out, vec![2]);
+        }
+
+        #[test]
+        fn handles_allocs() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "allocs": { "2": { "allocations": 3, "bytes": 64, "peak_bytes": 32 }, "0": { "allocations": 1, "bytes": 8, "peak_bytes": 8 } }, "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = &timings.data[0];
+            assert_eq!(
+                timing
+                    .allocs
+                    .iter()
+                    .map(|(part, _)| *part)
+                    .collect::<Vec<_>>(),
+                [0, 2]
+            );
+            assert_eq!(timing.allocs(2).map(|a| a.bytes), Some(64));
+            assert_eq!(timing.allocs(1), None);
+
+            let value = tinyjson::JsonValue::from(timing);
+            assert_eq!(Timing::try_from(&value).unwrap().allocs, timing.allocs);
+        }
+
+        #[test]
+        fn handles_exact_nanos() {
+            let json = r#"{ "data": [{ "day": "02", "part_1": "931.0ns", "part_2": "1.0µs", "nanos": { "1": 930.6, "2": 1004.4 }, "total_nanos": 1935 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = &timings.data[0];
+            assert_eq!(timing.nanos(1), Some(930.6));
+            assert_eq!(timing.nanos(2), Some(1004.4));
+            assert_eq!(timing.nanos(0), None);
+
+            let value = tinyjson::JsonValue::from(timing);
+            assert_eq!(Timing::try_from(&value).unwrap().nanos, timing.nanos);
+
+            // timings stored before exact times were recorded only have the rounded display time.
+            let json = r#"{ "data": [{ "day": "02", "part_1": "931.0ns", "part_2": null, "total_nanos": 931 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].nanos(1), Some(931.0));
         }
 
         #[test]
@@ -278,6 +554,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
+            template::runner::Statistic,
             template::timings::{Timing, Timings},
         };
 
@@ -288,11 +565,17 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 3_000_000_000_f64,
+                    statistic: Statistic::Mean,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -302,11 +585,17 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 1_000_000_000_f64,
+                    statistic: Statistic::Mean,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -316,17 +605,24 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 0.0,
+                    statistic: Statistic::Mean,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::runner::Statistic,
             template::timings::{Timing, Timings},
         };
 
@@ -340,7 +636,13 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 0_f64,
+                    statistic: Statistic::Mean,
                 }],
             };
             let merged = timings.merge(&other);
@@ -360,7 +662,13 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    parse: None,
+                    failed: vec![],
+                    timed_out: vec![],
+                    allocs: vec![],
+                    nanos: vec![],
                     total_nanos: 0_f64,
+                    statistic: Statistic::Mean,
                 }],
             };
             let merged = timings.merge(&other);
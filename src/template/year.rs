@@ -0,0 +1,383 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+use crate::template::Day;
+
+#[cfg(feature = "today")]
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// The environment variable that holds the default year, usually set in `.cargo/config.toml`.
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+/// The environment variable that overrides the length of individual seasons, e.g. `2026=25,2027=12`.
+pub const SEASON_DAYS_ENV: &str = "AOC_SEASON_DAYS";
+
+/// The known season lengths: every season from the given year on has the given number of days,
+/// until the next entry. Seasons 2015 to 2024 have 25 puzzles, advent of code announced 12 puzzles
+/// per season from 2025 on.
+const SEASONS: [(u16, u8); 2] = [(2015, 25), (2025, 12)];
+
+/// The value of `AOC_SEASON_DAYS` when the crate was compiled.
+/// It is read at compile time, so that `solution!` can check days against it in a const context.
+const SEASON_OVERRIDES: Option<&str> = option_env!("AOC_SEASON_DAYS");
+
+// fails compilation if `AOC_SEASON_DAYS` is malformed.
+const _: () = {
+    if let Some(overrides) = SEASON_OVERRIDES {
+        season_override(overrides, 0);
+    }
+};
+
+/// Returns the length of `year`'s season in `overrides`, a comma-separated list of `<year>=<days>`.
+/// Later entries win over earlier ones. Panics if `overrides` is malformed.
+const fn season_override(overrides: &str, year: u16) -> Option<u8> {
+    const INVALID: &str =
+        "invalid `AOC_SEASON_DAYS`, expecting a list like `2026=25,2027=12` with 1 to 25 days per year";
+
+    let bytes = overrides.as_bytes();
+    let mut found = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        while i < bytes.len() && bytes[i] == b' ' {
+            i += 1;
+        }
+
+        let start = i;
+        let mut entry_year: u32 = 0;
+        while i < bytes.len() && bytes[i].is_ascii_digit() && i - start < 4 {
+            entry_year = entry_year * 10 + (bytes[i] - b'0') as u32;
+            i += 1;
+        }
+        assert!(
+            i > start && i < bytes.len() && bytes[i] == b'=',
+            "{}",
+            INVALID
+        );
+        i += 1;
+
+        let start = i;
+        let mut days: u32 = 0;
+        while i < bytes.len() && bytes[i].is_ascii_digit() && i - start < 2 {
+            days = days * 10 + (bytes[i] - b'0') as u32;
+            i += 1;
+        }
+        while i < bytes.len() && bytes[i] == b' ' {
+            i += 1;
+        }
+        assert!(
+            i > start && days >= 1 && days <= 25 && (i == bytes.len() || bytes[i] == b','),
+            "{}",
+            INVALID
+        );
+        i += 1;
+
+        if entry_year == year as u32 {
+            found = Some(days as u8);
+        }
+    }
+
+    found
+}
+
+/// A year in which advent of code took place (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(2015..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub const fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the last day of this year's advent.
+    ///
+    /// The length is looked up in a table of the known seasons, and can be overridden per year with
+    /// `AOC_SEASON_DAYS` in `.cargo/config.toml`, e.g. if a future season is longer than announced.
+    /// The override is read when the crate is compiled, so that `solution!` can check the day of a
+    /// solution against the year in its file name at compile time.
+    ///
+    /// [`Day`] itself accepts 1 to 25, the days of the longest season. Parse a day of a specific
+    /// year with [`Day::parse_in`], or check it with [`YearDay::try_new`].
+    pub const fn last_day(self) -> Day {
+        if let Some(overrides) = SEASON_OVERRIDES {
+            if let Some(days) = season_override(overrides, self.0) {
+                return Day::__new_unchecked(days);
+            }
+        }
+
+        let mut days = SEASONS[0].1;
+        let mut i = 0;
+
+        while i < SEASONS.len() {
+            if self.0 >= SEASONS[i].0 {
+                days = SEASONS[i].1;
+            }
+            i += 1;
+        }
+
+        Day::__new_unchecked(days)
+    }
+
+    /// Returns `true` if `day` is part of this year's advent.
+    pub const fn has_day(self, day: Day) -> bool {
+        day.into_inner() <= self.last_day().into_inner()
+    }
+
+    /// Returns the year configured with the `AOC_YEAR` environment variable, if it is valid.
+    pub fn from_env() -> Option<Self> {
+        env::var(YEAR_ENV).ok()?.parse().ok()
+    }
+}
+
+#[cfg(feature = "today")]
+impl Year {
+    /// Returns the current year in the timezone of the advent of code server.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        Self::new(u16::try_from(today.year()).ok()?)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A day of a specific year's advent.
+///
+/// # Display
+/// This value displays as the name of the day's solution binary.
+///
+/// ```
+/// # use advent_of_code::{Day, Year, YearDay};
+/// let day = YearDay::new(Year::new(2024).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(day.to_string(), "2024_08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearDay {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl YearDay {
+    /// Creates a [`YearDay`] without checking that `day` is part of `year`'s advent.
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Creates a [`YearDay`] if `day` is part of `year`'s advent, returns an error otherwise.
+    pub fn try_new(year: Year, day: Day) -> Result<Self, DayOutOfSeasonError> {
+        if year.has_day(day) {
+            Ok(Self::new(year, day))
+        } else {
+            Err(DayOutOfSeasonError { year, day })
+        }
+    }
+
+    /// Creates a [`YearDay`] for the solution at `path`, which is named `<year>_<day>.rs`.
+    /// Panics if the file name does not start with a year or if `day` is not part of that year's advent,
+    /// which fails compilation in a const context.
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __from_path(path: &str, day: Day) -> Self {
+        let bytes = path.as_bytes();
+
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        assert!(
+            bytes.len() - start > 5 && bytes[start + 4] == b'_',
+            "solution files need to be named `<year>_<day>.rs`, e.g. `src/bin/2024_01.rs`"
+        );
+
+        let mut year = 0;
+        let mut i = start;
+
+        while i < start + 4 {
+            assert!(
+                bytes[i].is_ascii_digit(),
+                "solution files need to be named `<year>_<day>.rs`, e.g. `src/bin/2024_01.rs`"
+            );
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(
+            Year(year).has_day(day),
+            "the day of this solution is not part of its year's advent"
+        );
+
+        Self::new(Year(year), day)
+    }
+}
+
+impl Display for YearDay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.year, self.day)
+    }
+}
+
+/// An error which is returned when a day is not part of a year's advent.
+#[derive(Debug)]
+pub struct DayOutOfSeasonError {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Error for DayOutOfSeasonError {}
+
+impl Display for DayOutOfSeasonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {} is not part of advent of code {}, which ends on day {}",
+            self.day,
+            self.year,
+            self.year.last_day()
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{season_override, Year, YearDay};
+    use crate::{day, year};
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().unwrap(), year!(2024));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_years_from_solution_paths() {
+        let day = YearDay::__from_path("src/bin/2023_07.rs", day!(7));
+        assert_eq!(day, YearDay::new(year!(2023), day!(7)));
+
+        let day = YearDay::__from_path("C:\\aoc\\src\\bin\\2016_25.rs", day!(25));
+        assert_eq!(day.year, year!(2016));
+        assert_eq!(day.to_string(), "2016_25");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_paths_without_year() {
+        YearDay::__from_path("src/bin/07.rs", day!(7));
+    }
+
+    #[test]
+    fn checks_season_length() {
+        assert_eq!(year!(2024).last_day(), day!(25));
+        assert_eq!(year!(2025).last_day(), day!(12));
+        assert!(YearDay::try_new(year!(2024), day!(25)).is_ok());
+
+        let err = YearDay::try_new(year!(2025), day!(13)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 13 is not part of advent of code 2025, which ends on day 12"
+        );
+    }
+
+    #[test]
+    fn overrides_season_lengths() {
+        assert_eq!(season_override("", 2026), None);
+        assert_eq!(season_override("2026=25", 2026), Some(25));
+        assert_eq!(season_override("2026=25", 2027), None);
+        assert_eq!(season_override("2026=25, 2027=12,2026=10", 2026), Some(10));
+        assert_eq!(season_override("2026=25, 2027=12,2026=10", 2027), Some(12));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_long_season_overrides() {
+        season_override("2026=26", 2026);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_season_overrides() {
+        season_override("2026:25", 2026);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_out_of_season_solution_paths() {
+        YearDay::__from_path("src/bin/2025_13.rs", day!(13));
+    }
+}
@@ -0,0 +1,204 @@
+//! Simulation of a walker that moves across a [`Grid`] and turns in front of obstacles.
+
+use std::collections::HashSet;
+
+use crate::direction::Dir4;
+use crate::grid::Grid;
+use crate::point::Point;
+
+/// How a walk ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// The walker stepped off the grid.
+    Exited,
+    /// The walker returned to a state it was in before and would walk in circles forever.
+    Looped,
+}
+
+/// The result of a walk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Walk {
+    /// Every distinct cell that the walker stood on, including the start.
+    pub visited: HashSet<Point>,
+    pub outcome: Outcome,
+}
+
+/// A walker that moves straight ahead until the next cell is blocked, then turns and tries again.
+/// By default, it turns right.
+///
+/// The walker never modifies the grid. To test "what if" scenarios, e.g. an additional obstacle,
+/// use [`with_obstacle`](Walker::with_obstacle) instead of changing or cloning the grid.
+///
+/// ```
+/// # use advent_of_code::{direction::Dir4, grid::Grid, point::Point, walker::{Outcome, Walker}};
+/// let grid: Grid<char> = ".#.\n..#\n...".parse().unwrap();
+/// let walker = Walker::new(&grid, |c| *c == '#');
+/// let walk = walker.walk(Point::new(1, 2), Dir4::Up);
+/// assert_eq!(walk.outcome, Outcome::Exited);
+/// assert_eq!(walk.visited.len(), 2);
+/// ```
+pub struct Walker<'a, T, F> {
+    grid: &'a Grid<T>,
+    is_blocked: F,
+    turn: fn(Dir4) -> Dir4,
+    obstacle: Option<Point>,
+}
+
+impl<'a, T, F: Fn(&T) -> bool> Walker<'a, T, F> {
+    /// Creates a walker for `grid`, which cannot enter cells for which `is_blocked` returns `true`.
+    pub fn new(grid: &'a Grid<T>, is_blocked: F) -> Self {
+        Self {
+            grid,
+            is_blocked,
+            turn: Dir4::turn_right,
+            obstacle: None,
+        }
+    }
+
+    /// Changes how the walker turns in front of a blocked cell.
+    pub fn with_turn(self, turn: fn(Dir4) -> Dir4) -> Self {
+        Self { turn, ..self }
+    }
+
+    /// Returns a walker that also treats `obstacle` as blocked, without modifying the grid.
+    pub fn with_obstacle(&self, obstacle: Point) -> Self
+    where
+        F: Clone,
+    {
+        Self {
+            grid: self.grid,
+            is_blocked: self.is_blocked.clone(),
+            turn: self.turn,
+            obstacle: Some(obstacle),
+        }
+    }
+
+    /// Returns whether the cell at `pos` is blocked, or `None` if it lies outside the grid.
+    fn blocked(&self, pos: Point) -> Option<bool> {
+        let cell = self.grid.get(pos)?;
+        Some(self.obstacle == Some(pos) || (self.is_blocked)(cell))
+    }
+
+    /// Returns the state after a single move from `pos` in `dir`, or `None` if the walker leaves the grid.
+    /// In front of a blocked cell, the walker turns in place instead of moving.
+    pub fn step(&self, pos: Point, dir: Dir4) -> Option<(Point, Dir4)> {
+        let next = pos + dir.offset();
+        match self.blocked(next)? {
+            true => Some((pos, (self.turn)(dir))),
+            false => Some((next, dir)),
+        }
+    }
+
+    /// Returns every state of a walk from `pos` in `dir`, starting with that state.
+    /// The iterator ends when the walker leaves the grid, so it never ends if the walker loops.
+    pub fn states(&self, pos: Point, dir: Dir4) -> impl Iterator<Item = (Point, Dir4)> + '_ {
+        std::iter::successors(Some((pos, dir)), |(pos, dir)| self.step(*pos, *dir))
+    }
+
+    /// Walks from `pos` in `dir` until the walker leaves the grid or loops.
+    pub fn walk(&self, pos: Point, dir: Dir4) -> Walk {
+        let mut visited = HashSet::new();
+        let outcome = self.run(pos, dir, |pos| {
+            visited.insert(pos);
+        });
+        Walk { visited, outcome }
+    }
+
+    /// Walks from `pos` in `dir` like [`walk`](Walker::walk), but only returns how the walk ended.
+    pub fn outcome(&self, pos: Point, dir: Dir4) -> Outcome {
+        self.run(pos, dir, |_| {})
+    }
+
+    fn run(&self, mut pos: Point, mut dir: Dir4, mut on_visit: impl FnMut(Point)) -> Outcome {
+        // a loop has to contain a turn, so it is enough to remember the states at turns.
+        let mut turns: HashSet<(Point, Dir4)> = HashSet::new();
+        on_visit(pos);
+
+        loop {
+            let next = pos + dir.offset();
+            match self.blocked(next) {
+                None => return Outcome::Exited,
+                Some(true) => {
+                    if !turns.insert((pos, dir)) {
+                        return Outcome::Looped;
+                    }
+                    dir = (self.turn)(dir);
+                }
+                Some(false) => {
+                    pos = next;
+                    on_visit(pos);
+                }
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Outcome, Walker};
+    use crate::direction::Dir4;
+    use crate::grid::Grid;
+    use crate::point::Point;
+
+    fn lab() -> Grid<char> {
+        "....#.....\n.........#\n..........\n..#.......\n.......#..\n..........\n.#..^.....\n........#.\n#.........\n......#..."
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn walks_until_exiting() {
+        let lab = lab();
+        let walker = Walker::new(&lab, |c| *c == '#');
+        let walk = walker.walk(Point::new(4, 6), Dir4::Up);
+        assert_eq!(walk.outcome, Outcome::Exited);
+        assert_eq!(walk.visited.len(), 41);
+    }
+
+    #[test]
+    fn detects_loops_with_additional_obstacles() {
+        let lab = lab();
+        let walker = Walker::new(&lab, |c| *c == '#');
+        let start = Point::new(4, 6);
+
+        let looping = walker.with_obstacle(Point::new(3, 6));
+        assert_eq!(looping.outcome(start, Dir4::Up), Outcome::Looped);
+        assert_eq!(walker.outcome(start, Dir4::Up), Outcome::Exited);
+
+        let outside = walker.with_obstacle(Point::new(-1, 0));
+        assert_eq!(outside.outcome(start, Dir4::Up), Outcome::Exited);
+    }
+
+    #[test]
+    fn steps_and_turns() {
+        let grid: Grid<char> = ".#\n..".parse().unwrap();
+        let walker = Walker::new(&grid, |c| *c == '#');
+        let states: Vec<_> = walker.states(Point::new(0, 0), Dir4::Right).collect();
+        assert_eq!(
+            states,
+            [
+                (Point::new(0, 0), Dir4::Right),
+                (Point::new(0, 0), Dir4::Down),
+                (Point::new(0, 1), Dir4::Down),
+            ]
+        );
+
+        let left = Walker::new(&grid, |c| *c == '#').with_turn(Dir4::turn_left);
+        assert_eq!(
+            left.step(Point::new(0, 0), Dir4::Right),
+            Some((Point::new(0, 0), Dir4::Up))
+        );
+        assert_eq!(left.step(Point::new(0, 0), Dir4::Up), None);
+    }
+
+    #[test]
+    fn detects_loops_in_corners() {
+        let grid: Grid<char> = ".#.\n#.#\n.#.".parse().unwrap();
+        let walker = Walker::new(&grid, |c| *c == '#');
+        let walk = walker.walk(Point::new(1, 1), Dir4::Up);
+        assert_eq!(walk.outcome, Outcome::Looped);
+        assert_eq!(walk.visited.len(), 1);
+    }
+}